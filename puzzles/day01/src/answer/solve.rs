@@ -1,6 +1,4 @@
-use std::collections::HashMap;
-
-use crate::core::Value;
+use crate::core::{calibrate, Digitless, Scanner, Value, DIGITS, WORDS};
 
 use super::{Parsed1, Parsed2};
 
@@ -8,53 +6,18 @@ type Solution = Value;
 pub type Solution1 = Solution;
 pub type Solution2 = Solution;
 
-fn calibration_value(mut digits: impl Iterator<Item = Value>) -> Value {
-    let first = digits.next().unwrap();
-    let last = digits.last().unwrap_or(first);
-
-    first * 10 + last
-}
-
-fn solve(lines_of_digits: impl Iterator<Item = impl Iterator<Item = Value>>) -> Solution {
-    lines_of_digits.map(calibration_value).sum()
+pub fn solve_with(lines: &[&str], scanner: &Scanner) -> anyhow::Result<Solution> {
+    Ok(calibrate(lines, scanner, Digitless::Reject)?.sum)
 }
 
 pub fn solve1(lines: &Parsed1) -> anyhow::Result<Solution1> {
-    let lines_of_digits = lines
-        .iter()
-        .map(|s| s.chars().filter_map(|c| c.to_digit(10)));
-
-    Ok(solve(lines_of_digits))
+    let scanner = Scanner::new(DIGITS.iter().copied())?;
+    solve_with(lines, &scanner)
 }
 
 pub fn solve2(lines: &Parsed2) -> anyhow::Result<Solution2> {
-    let valid_digits = [
-        (1, "1", "one"),
-        (2, "2", "two"),
-        (3, "3", "three"),
-        (4, "4", "four"),
-        (5, "5", "five"),
-        (6, "6", "six"),
-        (7, "7", "seven"),
-        (8, "8", "eight"),
-        (9, "9", "nine"),
-    ]
-    .into_iter()
-    .fold(HashMap::new(), |mut acc, (digit, s, name)| {
-        acc.insert(s, digit);
-        acc.insert(name, digit);
-        acc
-    });
-
-    fn substrings(s: &str) -> impl Iterator<Item = &str> {
-        (0..s.len()).flat_map(move |i| (i + 1..=s.len()).map(move |j| &s[i..j]))
-    }
-
-    let lines_of_digits = lines
-        .iter()
-        .map(|s| substrings(s).filter_map(|s| valid_digits.get(s).cloned()));
-
-    Ok(solve(lines_of_digits))
+    let scanner = Scanner::new(DIGITS.iter().chain(WORDS).copied())?;
+    solve_with(lines, &scanner)
 }
 
 #[cfg(test)]
@@ -62,6 +25,7 @@ mod tests {
     use aoc::Input;
 
     use crate::answer::{parse1, parse2};
+    use crate::core::{Calibration, CalibrationError};
 
     use super::*;
 
@@ -79,4 +43,48 @@ mod tests {
         assert_eq!(solve2(&parse2(INPUT2)?)?, 281);
         Ok(())
     }
+
+    #[test]
+    fn test_digitless() -> anyhow::Result<()> {
+        let digits = Scanner::new(DIGITS.iter().copied())?;
+        let words = Scanner::new(DIGITS.iter().chain(WORDS).copied())?;
+
+        // A single digit is both the first and the last.
+        assert_eq!(solve1(&parse1("treb7uchet")?)?, 77);
+        assert_eq!(solve2(&parse2("xxsixxx")?)?, 66);
+
+        // Lines with only words have no digits unless the words count.
+        let lines = parse1("1abc2\ntwo\n")?;
+        let error = solve1(&lines).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<CalibrationError>(),
+            Some(&CalibrationError::NoDigits {
+                line: 2,
+                text: "two".to_string()
+            })
+        );
+        assert_eq!(error.to_string(), "line number 2 has no digits: \"two\"");
+        assert_eq!(solve2(&lines)?, 34);
+
+        // Empty lines never have digits.
+        let lines = ["1abc2", "", "pqr3stu8vwx", ""];
+        assert!(solve1(&lines.to_vec()).is_err());
+        assert!(solve2(&lines.to_vec()).is_err());
+        assert_eq!(
+            calibrate(&lines, &digits, Digitless::Skip)?,
+            Calibration {
+                sum: 50,
+                skipped: 2
+            }
+        );
+        assert_eq!(
+            calibrate(&["one", "", "nine"], &words, Digitless::Skip)?,
+            Calibration {
+                sum: 110,
+                skipped: 1
+            }
+        );
+
+        Ok(())
+    }
 }
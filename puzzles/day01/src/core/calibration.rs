@@ -0,0 +1,61 @@
+use super::{Scanner, Value};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Digitless {
+    #[default]
+    Reject,
+    Skip,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalibrationError {
+    // Lines are numbered from 1.
+    NoDigits { line: usize, text: String },
+}
+
+impl std::fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NoDigits { line, text } => {
+                write!(f, "line number {} has no digits: {:?}", line, text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Calibration {
+    pub sum: Value,
+    pub skipped: usize,
+}
+
+pub fn calibration_value(scanner: &Scanner, line: &str) -> Option<Value> {
+    let (first, last) = scanner.first_and_last(line)?;
+
+    Some(first * 10 + last)
+}
+
+pub fn calibrate(
+    lines: &[&str],
+    scanner: &Scanner,
+    digitless: Digitless,
+) -> Result<Calibration, CalibrationError> {
+    let mut calibration = Calibration::default();
+
+    for (i, line) in lines.iter().enumerate() {
+        match (calibration_value(scanner, line), digitless) {
+            (Some(value), _) => calibration.sum += value,
+            (None, Digitless::Skip) => calibration.skipped += 1,
+            (None, Digitless::Reject) => {
+                return Err(CalibrationError::NoDigits {
+                    line: i + 1,
+                    text: line.to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(calibration)
+}
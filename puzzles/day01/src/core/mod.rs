@@ -1 +1,7 @@
 pub type Value = u32;
+
+pub mod scanner;
+pub use scanner::*;
+
+pub mod calibration;
+pub use calibration::*;
@@ -0,0 +1,245 @@
+use anyhow::ensure;
+
+use std::collections::{HashMap, VecDeque};
+
+use super::Value;
+
+pub type Vocabulary<'a> = [(&'a str, Value)];
+
+pub const DIGITS: &Vocabulary = &[
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+pub const WORDS: &Vocabulary = &[
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    next: HashMap<u8, usize>,
+    fail: usize,
+    // Every token that ends here, including the ones that end with the text leading up to it,
+    // as (length, value).
+    outputs: Vec<(usize, Value)>,
+}
+
+// https://en.wikipedia.org/wiki/Aho%E2%80%93Corasick_algorithm
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    nodes: Vec<Node>,
+}
+
+impl Scanner {
+    // Only the lengths of the tokens are kept, so they can come from anywhere.
+    pub fn new<S: AsRef<str>>(
+        vocabulary: impl IntoIterator<Item = (S, Value)>,
+    ) -> anyhow::Result<Self> {
+        let mut nodes = vec![Node::default()];
+
+        for (token, value) in vocabulary {
+            let token = token.as_ref();
+
+            ensure!(!token.is_empty(), "empty token for value {}", value);
+
+            let mut state = ROOT;
+
+            for &byte in token.as_bytes() {
+                state = match nodes[state].next.get(&byte) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[state].next.insert(byte, next);
+                        next
+                    }
+                };
+            }
+
+            if let Some(&(_, other)) = nodes[state].outputs.first() {
+                ensure!(
+                    other == value,
+                    "token {:?} has conflicting values {} and {}",
+                    token,
+                    other,
+                    value
+                );
+                continue;
+            }
+
+            nodes[state].outputs.push((token.len(), value));
+        }
+
+        // Filling in the failure links breadth first means that every shorter suffix has already
+        // been linked by the time a node needs it.
+        let mut queue: VecDeque<_> = nodes[ROOT].next.values().copied().collect();
+
+        while let Some(state) = queue.pop_front() {
+            let edges: Vec<_> = nodes[state]
+                .next
+                .iter()
+                .map(|(&byte, &next)| (byte, next))
+                .collect();
+
+            for (byte, next) in edges {
+                let mut fail = nodes[state].fail;
+
+                while fail != ROOT && !nodes[fail].next.contains_key(&byte) {
+                    fail = nodes[fail].fail;
+                }
+
+                let fail = nodes[fail].next.get(&byte).copied().unwrap_or(ROOT);
+
+                nodes[next].fail = fail;
+
+                let inherited = nodes[fail].outputs.clone();
+                nodes[next].outputs.extend(inherited);
+
+                queue.push_back(next);
+            }
+        }
+
+        Ok(Self { nodes })
+    }
+
+    fn step(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[state].next.get(&byte) {
+                return next;
+            }
+
+            if state == ROOT {
+                return ROOT;
+            }
+
+            state = self.nodes[state].fail;
+        }
+    }
+
+    // Every token in the text, including the ones that overlap, in the order that they end.
+    pub fn matches<'a>(&'a self, s: &'a str) -> impl Iterator<Item = Match> + 'a {
+        s.bytes()
+            .enumerate()
+            .scan(ROOT, move |state, (i, byte)| {
+                *state = self.step(*state, byte);
+                Some((i + 1, &self.nodes[*state].outputs))
+            })
+            .flat_map(|(end, outputs)| {
+                outputs.iter().map(move |&(len, value)| Match {
+                    start: end - len,
+                    end,
+                    value,
+                })
+            })
+    }
+
+    // The values of the tokens that start first and last in the text, in a single pass over it.
+    pub fn first_and_last(&self, s: &str) -> Option<(Value, Value)> {
+        let key = |m: &Match| (m.start, m.end);
+
+        self.matches(s)
+            .fold(None, |bounds: Option<(Match, Match)>, m| match bounds {
+                None => Some((m, m)),
+                Some((first, last)) => Some((
+                    if key(&m) < key(&first) { m } else { first },
+                    if key(&m) > key(&last) { m } else { last },
+                )),
+            })
+            .map(|(first, last)| (first.value, last.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches() -> anyhow::Result<()> {
+        let scanner = Scanner::new(DIGITS.iter().chain(WORDS).copied())?;
+
+        let values: Vec<_> = scanner.matches("eightwothree").map(|m| m.value).collect();
+        assert_eq!(values, [8, 2, 3]);
+
+        let values: Vec<_> = scanner.matches("zoneight234").map(|m| m.value).collect();
+        assert_eq!(values, [1, 8, 2, 3, 4]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_and_last() -> anyhow::Result<()> {
+        let scanner = Scanner::new(DIGITS.iter().chain(WORDS).copied())?;
+
+        assert_eq!(scanner.first_and_last("eightwo"), Some((8, 2)));
+        assert_eq!(scanner.first_and_last("xtwone3four"), Some((2, 4)));
+        assert_eq!(scanner.first_and_last("7"), Some((7, 7)));
+        assert_eq!(scanner.first_and_last("abc"), None);
+
+        // A token that starts first can still end after one that starts later.
+        let scanner = Scanner::new([("abcd", 1), ("bc", 2)])?;
+        assert_eq!(scanner.first_and_last("abcd"), Some((1, 2)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_vocabulary() -> anyhow::Result<()> {
+        let german = [
+            ("eins", 1),
+            ("zwei", 2),
+            ("drei", 3),
+            ("vier", 4),
+            ("fünf", 5),
+            ("sechs", 6),
+            ("sieben", 7),
+            ("acht", 8),
+            ("neun", 9),
+        ];
+
+        let scanner = Scanner::new(DIGITS.iter().chain(&german).copied())?;
+
+        assert_eq!(scanner.first_and_last("zweinsieben"), Some((2, 7)));
+        assert_eq!(scanner.first_and_last("xfünf3achtzehn"), Some((5, 8)));
+
+        // Word lists do not have to be known ahead of time.
+        let spanish: Vec<_> = "uno 1\ndos 2\ntres 3\n"
+            .lines()
+            .map(|line| {
+                let (word, value) = line.split_once(' ').unwrap();
+                (word.to_string(), value.parse().unwrap())
+            })
+            .collect();
+
+        let scanner = Scanner::new(spanish)?;
+        assert_eq!(scanner.first_and_last("cuatrodosunotres"), Some((2, 3)));
+
+        assert!(Scanner::new([("", 1)]).is_err());
+        assert!(Scanner::new([("one", 1), ("one", 2)]).is_err());
+
+        Ok(())
+    }
+}
@@ -1,42 +1,34 @@
 use anyhow::Context;
 
-use std::collections::HashMap;
-
 use aoc::Input;
 
-use crate::core::{Game, RGB};
+use crate::core::{Color, Draw, Game, UnknownColors};
 
 type Parsed = Vec<Game>;
 pub type Parsed1 = Parsed;
 pub type Parsed2 = Parsed;
 
-fn parse(input: Input) -> anyhow::Result<Parsed> {
-    fn parse_cubes(s: &str) -> anyhow::Result<(&str, usize)> {
+pub fn parse_with(input: Input, unknown: UnknownColors) -> anyhow::Result<Parsed> {
+    let parse_cubes = |s: &str| -> anyhow::Result<(Color, usize)> {
         let (left, color) = s
             .trim()
             .split_once(' ')
             .context("expected cube count/color to be separated by a space")?;
 
         let count = left.parse()?;
+        let color = Color::parse(color, unknown)?;
 
         Ok((color, count))
-    }
+    };
 
-    fn parse_rgb(s: &str) -> anyhow::Result<RGB> {
-        let mut map = s
-            .split(',')
+    let parse_draw = |s: &str| -> anyhow::Result<Draw> {
+        s.split(',')
             .enumerate()
             .map(|(i, s)| parse_cubes(s).with_context(|| format!("color number {}", i + 1)))
-            .collect::<Result<HashMap<_, _>, _>>()?;
+            .collect()
+    };
 
-        let red = map.remove("red").unwrap_or_default();
-        let green = map.remove("green").unwrap_or_default();
-        let blue = map.remove("blue").unwrap_or_default();
-
-        Ok([red, green, blue])
-    }
-
-    fn parse_game(s: &str) -> anyhow::Result<Game> {
+    let parse_game = |s: &str| -> anyhow::Result<Game> {
         let (left, right) = s
             .split_once(": ")
             .context("expected game id/subsets to be separated by a colon")?;
@@ -47,14 +39,14 @@ fn parse(input: Input) -> anyhow::Result<Parsed> {
 
         let id = left.parse()?;
 
-        let subsets = right
+        let draws = right
             .split(';')
             .enumerate()
-            .map(|(i, s)| parse_rgb(s).with_context(|| format!("subset number {}", i + 1)))
+            .map(|(i, s)| parse_draw(s).with_context(|| format!("subset number {}", i + 1)))
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok((id, subsets))
-    }
+        Ok(Game { id, draws })
+    };
 
     input
         .lines()
@@ -64,11 +56,11 @@ fn parse(input: Input) -> anyhow::Result<Parsed> {
 }
 
 pub fn parse1(input: Input) -> anyhow::Result<Parsed1> {
-    parse(input)
+    parse_with(input, UnknownColors::Reject)
 }
 
 pub fn parse2(input: Input) -> anyhow::Result<Parsed2> {
-    parse(input)
+    parse_with(input, UnknownColors::Reject)
 }
 
 #[cfg(test)]
@@ -81,7 +73,21 @@ mod tests {
 
     #[test]
     fn test_parse() -> anyhow::Result<()> {
-        dbg!(parse(INPUT)?);
+        dbg!(parse1(INPUT)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_colors() -> anyhow::Result<()> {
+        let input = "Game 1: 3 blue, 4 purple; 1 red\n";
+
+        assert!(parse1(input).is_err());
+
+        let games = parse_with(input, UnknownColors::Keep)?;
+        let purple = Color::Other("purple".to_string());
+        assert_eq!(games[0].draws[0].get(&purple), 4);
+        assert_eq!(games[0].draws[0].get(&Color::Blue), 3);
+
         Ok(())
     }
 }
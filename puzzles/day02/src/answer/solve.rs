@@ -1,4 +1,4 @@
-use crate::core::RGB_LEN;
+use crate::core::{Bag, Color, GameList};
 
 use super::{Parsed1, Parsed2};
 
@@ -7,46 +7,23 @@ pub type Solution1 = Solution;
 pub type Solution2 = Solution;
 
 pub fn solve1(games: &Parsed1) -> anyhow::Result<Solution1> {
-    let available_cubes = [12, 13, 14];
-
-    Ok(games
-        .iter()
-        .filter_map(|(id, subsets)| {
-            subsets
-                .iter()
-                .all(|subset| {
-                    subset
-                        .iter()
-                        .zip(available_cubes.iter())
-                        .all(|(count, total)| count <= total)
-                })
-                .then_some(id)
-        })
-        .sum())
+    let bag: Bag = [(Color::Red, 12), (Color::Green, 13), (Color::Blue, 14)]
+        .into_iter()
+        .collect();
+
+    Ok(games.feasible(&bag).map(|game| game.id).sum())
 }
 
 pub fn solve2(games: &Parsed2) -> anyhow::Result<Solution2> {
-    Ok(games
-        .iter()
-        .map(|(_, subsets)| {
-            (0..RGB_LEN)
-                .map(|i| {
-                    subsets
-                        .iter()
-                        .map(|subset| subset[i])
-                        .max()
-                        .unwrap_or_default()
-                })
-                .product::<usize>()
-        })
-        .sum())
+    Ok(games.minimum_bags().map(|(_, bag)| bag.power()).sum())
 }
 
 #[cfg(test)]
 mod tests {
     use aoc::Input;
 
-    use crate::answer::{parse1, parse2};
+    use crate::answer::{parse1, parse2, parse_with};
+    use crate::core::UnknownColors;
 
     use super::*;
 
@@ -63,4 +40,39 @@ mod tests {
         assert_eq!(solve2(&parse2(INPUT)?)?, 2286);
         Ok(())
     }
+
+    #[test]
+    fn test_queries() -> anyhow::Result<()> {
+        let games = parse1(INPUT)?;
+
+        let bag: Bag = [(Color::Red, 6), (Color::Green, 3), (Color::Blue, 6)]
+            .into_iter()
+            .collect();
+        let ids: Vec<_> = games.feasible(&bag).map(|game| game.id).collect();
+        assert_eq!(ids, [1, 2, 5]);
+
+        let powers: Vec<_> = games
+            .minimum_bags()
+            .map(|(id, bag)| (id, bag.power()))
+            .collect();
+        assert_eq!(powers, [(1, 48), (2, 12), (3, 1560), (4, 630), (5, 36)]);
+
+        let smallest = games.smallest_bag(&[1, 2, 5])?;
+        assert_eq!(smallest, bag);
+        assert_eq!(games.feasible(&smallest).count(), 3);
+
+        assert_eq!(games.smallest_bag(&[])?, Bag::default());
+        assert!(games.smallest_bag(&[6]).is_err());
+
+        // Colors other than red, green and blue count towards feasibility, but not power.
+        let games = parse_with(INPUT, UnknownColors::Keep)?;
+        let games = [
+            games[0].clone(),
+            parse_with("Game 6: 1 red, 2 purple", UnknownColors::Keep)?[0].clone(),
+        ];
+        assert_eq!(games.feasible(&bag).count(), 1);
+        assert_eq!(games[1].minimum_bag().power(), 0);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,53 @@
+use anyhow::bail;
+
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+    Other(String),
+}
+
+pub const RGB: [Color; 3] = [Color::Red, Color::Green, Color::Blue];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum UnknownColors {
+    #[default]
+    Reject,
+    Keep,
+}
+
+impl Color {
+    pub fn parse(s: &str, unknown: UnknownColors) -> anyhow::Result<Self> {
+        match (Self::try_from(s), unknown) {
+            (Err(_), UnknownColors::Keep) => Ok(Self::Other(s.to_string())),
+            (result, _) => result,
+        }
+    }
+}
+
+impl TryFrom<&str> for Color {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "red" => Ok(Self::Red),
+            "green" => Ok(Self::Green),
+            "blue" => Ok(Self::Blue),
+            _ => bail!("unknown color: {:?}", s),
+        }
+    }
+}
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Red => write!(f, "red"),
+            Self::Green => write!(f, "green"),
+            Self::Blue => write!(f, "blue"),
+            Self::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
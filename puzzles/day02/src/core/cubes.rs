@@ -0,0 +1,56 @@
+use std::collections::BTreeMap;
+
+use super::{Color, RGB};
+
+// A number of cubes of each color, whether they were drawn in a game or are held in a bag.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cubes(BTreeMap<Color, usize>);
+
+pub type Draw = Cubes;
+pub type Bag = Cubes;
+
+impl Cubes {
+    pub fn get(&self, color: &Color) -> usize {
+        self.0.get(color).copied().unwrap_or_default()
+    }
+
+    pub fn add(&mut self, color: Color, count: usize) {
+        *self.0.entry(color).or_default() += count;
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Color, usize)> {
+        self.0.iter().map(|(color, &count)| (color, count))
+    }
+
+    pub fn fits_in(&self, bag: &Bag) -> bool {
+        self.iter().all(|(color, count)| count <= bag.get(color))
+    }
+
+    // The fewest cubes of each color that could hold either.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+
+        for (color, count) in other.iter() {
+            let entry = union.0.entry(color.clone()).or_default();
+            *entry = (*entry).max(count);
+        }
+
+        union
+    }
+
+    pub fn power(&self) -> usize {
+        RGB.iter().map(|color| self.get(color)).product()
+    }
+}
+
+impl FromIterator<(Color, usize)> for Cubes {
+    fn from_iter<T: IntoIterator<Item = (Color, usize)>>(iter: T) -> Self {
+        let mut cubes = Self::default();
+
+        for (color, count) in iter {
+            cubes.add(color, count);
+        }
+
+        cubes
+    }
+}
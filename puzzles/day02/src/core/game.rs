@@ -0,0 +1,50 @@
+use anyhow::Context;
+
+use super::{Bag, Cubes, Draw};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: usize,
+    pub draws: Vec<Draw>,
+}
+
+impl Game {
+    pub fn is_feasible(&self, bag: &Bag) -> bool {
+        self.draws.iter().all(|draw| draw.fits_in(bag))
+    }
+
+    pub fn minimum_bag(&self) -> Bag {
+        self.draws
+            .iter()
+            .fold(Cubes::default(), |bag, draw| bag.union(draw))
+    }
+}
+
+pub trait GameList {
+    fn feasible<'a>(&'a self, bag: &'a Bag) -> impl Iterator<Item = &'a Game> + 'a;
+
+    fn minimum_bags(&self) -> impl Iterator<Item = (usize, Bag)> + '_;
+
+    fn smallest_bag(&self, ids: &[usize]) -> anyhow::Result<Bag>;
+}
+
+impl GameList for [Game] {
+    fn feasible<'a>(&'a self, bag: &'a Bag) -> impl Iterator<Item = &'a Game> + 'a {
+        self.iter().filter(move |game| game.is_feasible(bag))
+    }
+
+    fn minimum_bags(&self) -> impl Iterator<Item = (usize, Bag)> + '_ {
+        self.iter().map(|game| (game.id, game.minimum_bag()))
+    }
+
+    fn smallest_bag(&self, ids: &[usize]) -> anyhow::Result<Bag> {
+        ids.iter().try_fold(Cubes::default(), |bag, &id| {
+            let game = self
+                .iter()
+                .find(|game| game.id == id)
+                .with_context(|| format!("no game with id {}", id))?;
+
+            Ok(bag.union(&game.minimum_bag()))
+        })
+    }
+}
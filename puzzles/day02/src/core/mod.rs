@@ -1,4 +1,8 @@
-pub const RGB_LEN: usize = 3;
+pub mod color;
+pub use color::*;
 
-pub type RGB = [usize; RGB_LEN];
-pub type Game = (usize, Vec<RGB>);
+pub mod cubes;
+pub use cubes::*;
+
+pub mod game;
+pub use game::*;
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use super::{Location, Schematic, Symbol, Value};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Number {
+    pub value: Value,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
+
+impl Number {
+    // Every location touching the number, including diagonally, that could be in the schematic.
+    pub fn neighborhood(&self) -> impl Iterator<Item = Location> + '_ {
+        let rows = self.row.saturating_sub(1)..=self.row + 1;
+        let columns = self.columns.start.saturating_sub(1)..=self.columns.end;
+
+        rows.flat_map(move |row| columns.clone().map(move |column| (row, column)))
+            .filter(|&(row, column)| row != self.row || !self.columns.contains(&column))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SymbolSpace {
+    pub symbol: Symbol,
+    pub location: Location,
+}
+
+// All of the numbers and symbols in a schematic, in reading order, and which of them touch.
+#[derive(Debug, Clone, Default)]
+pub struct EntityIndex {
+    numbers: Vec<Number>,
+    symbols: Vec<SymbolSpace>,
+    symbols_by_number: Vec<Vec<usize>>,
+    numbers_by_symbol: Vec<Vec<usize>>,
+}
+
+impl EntityIndex {
+    pub fn new(schematic: &Schematic) -> Self {
+        let (height, width) = schematic.shape();
+
+        let mut numbers = Vec::new();
+        let mut symbols = Vec::new();
+
+        for row in 0..height {
+            let mut number: Option<Number> = None;
+
+            for column in 0..width {
+                let space = &schematic[(row, column)];
+
+                if let Some(&digit) = space.digit() {
+                    let number = number.get_or_insert(Number {
+                        value: 0,
+                        row,
+                        columns: column..column,
+                    });
+
+                    number.value = number.value * 10 + digit as Value;
+                    number.columns.end = column + 1;
+
+                    continue;
+                }
+
+                numbers.extend(number.take());
+
+                if let Some(&symbol) = space.symbol() {
+                    symbols.push(SymbolSpace {
+                        symbol,
+                        location: (row, column),
+                    });
+                }
+            }
+
+            numbers.extend(number);
+        }
+
+        let symbol_at: HashMap<_, _> = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, symbol)| (symbol.location, i))
+            .collect();
+
+        let mut symbols_by_number = vec![Vec::new(); numbers.len()];
+        let mut numbers_by_symbol = vec![Vec::new(); symbols.len()];
+
+        for (i, number) in numbers.iter().enumerate() {
+            for location in number.neighborhood() {
+                if let Some(&j) = symbol_at.get(&location) {
+                    symbols_by_number[i].push(j);
+                    numbers_by_symbol[j].push(i);
+                }
+            }
+        }
+
+        Self {
+            numbers,
+            symbols,
+            symbols_by_number,
+            numbers_by_symbol,
+        }
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[SymbolSpace] {
+        &self.symbols
+    }
+
+    pub fn symbols_next_to(&self, number: usize) -> impl Iterator<Item = &SymbolSpace> + '_ {
+        self.symbols_by_number[number]
+            .iter()
+            .map(|&i| &self.symbols[i])
+    }
+
+    pub fn numbers_next_to(&self, symbol: usize) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers_by_symbol[symbol]
+            .iter()
+            .map(|&i| &self.numbers[i])
+    }
+
+    // Each number is only listed once, no matter how many matching symbols it touches.
+    pub fn numbers_next_to_symbol(&self, symbol: Symbol) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(move |&(i, _)| self.symbols_next_to(i).any(|other| other.symbol == symbol))
+            .map(|(_, number)| number)
+    }
+
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> + '_ {
+        self.numbers
+            .iter()
+            .zip(&self.symbols_by_number)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    pub fn gears(&self) -> impl Iterator<Item = (&SymbolSpace, [&Number; 2])> + '_ {
+        self.symbols
+            .iter()
+            .zip(&self.numbers_by_symbol)
+            .filter(|(symbol, _)| symbol.symbol == '*')
+            .filter_map(|(symbol, numbers)| match numbers[..] {
+                [a, b] => Some((symbol, [&self.numbers[a], &self.numbers[b]])),
+                _ => None,
+            })
+    }
+}
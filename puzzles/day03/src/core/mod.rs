@@ -1,7 +1,11 @@
 pub type Value = u64;
+pub type Location = (usize, usize);
 
 pub mod space;
 pub use space::*;
 
+pub mod index;
+pub use index::*;
+
 pub mod schematic;
 pub use schematic::*;
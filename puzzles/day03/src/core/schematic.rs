@@ -1,132 +1,45 @@
-use anyhow::Context;
+use anyhow::{ensure, Context};
 
-use std::collections::{HashSet, VecDeque};
 use std::convert::TryFrom;
 use std::ops::Index;
 
-use super::{Digit, Space, Symbol, Value};
+use super::{EntityIndex, Location, Space, Value};
 
-type Location = (usize, usize);
-type Direction = (isize, isize);
-type Cell<'a, T> = (Location, &'a T);
 type Inner = nalgebra::DMatrix<Space>;
 
-const DIRECTIONS: [Direction; 8] = [
-    (-1, -1), // Northwest
-    (-1, 0),  // North
-    (-1, 1),  // Northeast
-    (0, -1),  // West
-    (0, 1),   // East
-    (1, -1),  // Southwest
-    (1, 0),   // South
-    (1, 1),   // Southeast
-];
-
-fn adjacent_locations((row, column): Location) -> impl Iterator<Item = Location> {
-    DIRECTIONS
-        .into_iter()
-        .filter_map(move |(vertical, horizontal)| {
-            let row = row.checked_add_signed(vertical)?;
-            let column = column.checked_add_signed(horizontal)?;
-            Some((row, column))
-        })
-}
-
-fn digits_to_number(digits: impl IntoIterator<Item = Digit>) -> Value {
-    digits.into_iter().fold(0, |mut number, digit| {
-        number *= 10;
-        number += digit as Value;
-        number
-    })
-}
-
 #[derive(Debug, Clone)]
-pub struct Schematic(Inner);
+pub struct Schematic {
+    inner: Inner,
+    index: EntityIndex,
+}
 
 impl Index<Location> for Schematic {
     type Output = Space;
 
-    fn index(&self, (row, column): Location) -> &Self::Output {
-        &self.0[(column, row)]
+    fn index(&self, location: Location) -> &Self::Output {
+        &self.inner[location]
     }
 }
 
 impl Schematic {
-    fn get(&self, (row, column): Location) -> Option<&Space> {
-        self.0.get((column, row))
+    pub fn get(&self, location: Location) -> Option<&Space> {
+        self.inner.get(location)
     }
 
-    fn locations(&self) -> impl Iterator<Item = Location> {
-        let (height, width) = self.0.shape();
-        (0..height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    pub fn shape(&self) -> (usize, usize) {
+        self.inner.shape()
     }
 
-    fn symbols(&self) -> impl Iterator<Item = Cell<Symbol>> {
-        self.locations()
-            .filter_map(|location| self[location].symbol().map(|symbol| (location, symbol)))
-    }
-
-    fn adjacent_digit_groups(&self, location: Location) -> impl Iterator<Item = Vec<Digit>> + '_ {
-        let mut queue: Vec<_> = adjacent_locations(location).collect();
-        let mut seen = HashSet::new();
-
-        let width = self.0.ncols();
-
-        std::iter::from_fn(move || {
-            while let Some(location) = queue.pop() {
-                if !seen.insert(location) {
-                    continue;
-                }
-
-                let maybe_digit = self.get(location).and_then(|space| space.digit());
-
-                if maybe_digit.is_none() {
-                    continue;
-                }
-
-                let (row, column) = location;
-
-                let mut group = VecDeque::from([maybe_digit.copied().unwrap()]);
-
-                for column in (0..column).rev() {
-                    let location = (row, column);
-                    if let Some(digit) = self.get(location).and_then(|space| space.digit()) {
-                        group.push_front(*digit);
-                        seen.insert(location);
-                    } else {
-                        break;
-                    }
-                }
-
-                for column in column + 1..width {
-                    let location = (row, column);
-                    if let Some(digit) = self.get(location).and_then(|space| space.digit()) {
-                        group.push_back(*digit);
-                        seen.insert(location);
-                    } else {
-                        break;
-                    }
-                }
-
-                return Some(group.into_iter().collect());
-            }
-
-            None
-        })
+    pub fn entities(&self) -> &EntityIndex {
+        &self.index
     }
 
     pub fn part_numbers(&self) -> impl Iterator<Item = Value> + '_ {
-        self.symbols()
-            .flat_map(|(location, _)| self.adjacent_digit_groups(location).map(digits_to_number))
+        self.index.part_numbers().map(|number| number.value)
     }
 
     pub fn gear_ratios(&self) -> impl Iterator<Item = Value> + '_ {
-        self.symbols()
-            .filter(|(_, &symbol)| symbol == '*')
-            .flat_map(|(location, _)| {
-                let groups: Vec<_> = self.adjacent_digit_groups(location).collect();
-                (groups.len() == 2).then_some(groups.into_iter().map(digits_to_number).product())
-            })
+        self.index.gears().map(|(_, [a, b])| a.value * b.value)
     }
 }
 
@@ -134,31 +47,49 @@ impl TryFrom<&str> for Schematic {
     type Error = anyhow::Error;
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        let mut lines = s.lines().enumerate().peekable();
+        let lines: Vec<_> = s.lines().collect();
 
         let width = lines
-            .peek()
-            .map(|(_, first_line)| first_line.len())
+            .first()
+            .map(|first_line| first_line.chars().count())
             .context("empty input")?;
 
-        let spaces = lines
-            .flat_map(|(i, s)| {
-                s.chars().enumerate().map(move |(j, c)| {
-                    c.try_into()
-                        .with_context(|| format!("row number {}, column number {}", i + 1, j + 1))
-                })
-            })
-            .collect::<Result<Vec<Space>, _>>()?;
+        ensure!(width > 0, "empty first line");
 
-        let height = spaces.len() / width;
-        let inner = Inner::from_vec(height, width, spaces);
+        let mut spaces = Vec::with_capacity(lines.len() * width);
 
-        Ok(Self(inner))
+        for (i, line) in lines.iter().enumerate() {
+            let len = spaces.len();
+            spaces.extend(line.chars().map(Space::from));
+
+            ensure!(
+                spaces.len() - len == width,
+                "expected row number {} to have {} spaces, but it had {}",
+                i + 1,
+                width,
+                spaces.len() - len
+            );
+        }
+
+        // The spaces are read in row by row, but the matrix would otherwise fill in column by
+        // column.
+        let inner = Inner::from_row_slice(lines.len(), width, &spaces);
+
+        let mut schematic = Self {
+            inner,
+            index: Default::default(),
+        };
+
+        schematic.index = EntityIndex::new(&schematic);
+
+        Ok(schematic)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::core::{Number, SymbolSpace};
+
     use super::*;
 
     const INPUT: &str = include_str!("../../input-test");
@@ -177,7 +108,7 @@ mod tests {
             };
         }
 
-        test!(INPUT, [35, 467, 633, 617, 592, 664, 598, 755]);
+        test!(INPUT, [467, 35, 633, 617, 592, 755, 664, 598]);
     }
 
     #[test]
@@ -196,4 +127,79 @@ mod tests {
 
         test!(INPUT, [16345, 451490]);
     }
+
+    #[test]
+    fn test_entities() -> anyhow::Result<()> {
+        let schematic = Schematic::try_from(INPUT)?;
+        let entities = schematic.entities();
+
+        assert_eq!(entities.numbers().len(), 10);
+        assert_eq!(entities.symbols().len(), 6);
+
+        assert_eq!(
+            entities.numbers()[0],
+            Number {
+                value: 467,
+                row: 0,
+                columns: 0..3
+            }
+        );
+        assert_eq!(
+            entities.symbols()[0],
+            SymbolSpace {
+                symbol: '*',
+                location: (1, 3)
+            }
+        );
+
+        let values = |numbers: Vec<&Number>| -> Vec<_> {
+            numbers.into_iter().map(|number| number.value).collect()
+        };
+
+        assert_eq!(values(entities.numbers_next_to(0).collect()), [467, 35]);
+        assert_eq!(
+            values(entities.numbers_next_to_symbol('#').collect()),
+            [633]
+        );
+        assert_eq!(values(entities.numbers_next_to_symbol('!').collect()), []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_shared_number() -> anyhow::Result<()> {
+        // A number touching two symbols is still only one part number.
+        let schematic = Schematic::try_from("*..\n12.\n..#")?;
+        assert_eq!(schematic.part_numbers().collect::<Vec<_>>(), [12]);
+        assert_eq!(schematic.entities().symbols_next_to(0).count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rectangular() -> anyhow::Result<()> {
+        let wide = Schematic::try_from("12.....*..\n..*..7..34\n")?;
+        assert_eq!(wide.shape(), (2, 10));
+        assert_eq!(wide[(1, 2)], Space::Symbol('*'));
+        assert_eq!(wide.get((0, 9)), Some(&Space::Empty));
+        assert_eq!(wide.get((2, 0)), None);
+        assert_eq!(wide.part_numbers().collect::<Vec<_>>(), [12, 34]);
+        assert_eq!(wide.gear_ratios().collect::<Vec<_>>(), [] as [Value; 0]);
+
+        let tall = Schematic::try_from("1.\n*.\n.2\n..\n3.\n#4\n")?;
+        assert_eq!(tall.shape(), (6, 2));
+        assert_eq!(tall[(5, 0)], Space::Symbol('#'));
+        assert_eq!(tall.get((0, 2)), None);
+        assert_eq!(tall.part_numbers().collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert_eq!(tall.gear_ratios().collect::<Vec<_>>(), [2]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ragged() {
+        assert!(Schematic::try_from("..1\n.*\n...").is_err());
+        assert!(Schematic::try_from("..1\n.*..\n...").is_err());
+        assert!(Schematic::try_from("").is_err());
+        assert!(Schematic::try_from("\n..").is_err());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::core::Score;
+use crate::core::{copy_counts, Score};
 
 use super::{Parsed1, Parsed2};
 
@@ -10,23 +10,7 @@ pub fn solve1(cards: &Parsed1) -> anyhow::Result<Solution1> {
 }
 
 pub fn solve2(cards: &Parsed2) -> anyhow::Result<Solution2> {
-    let mut count = 0;
-    let mut queue: Vec<_> = cards.iter().enumerate().collect();
-
-    while let Some((i, card)) = queue.pop() {
-        count += 1;
-
-        let match_count = card.matching_numbers().count();
-
-        cards
-            .iter()
-            .enumerate()
-            .skip(i + 1)
-            .take(match_count)
-            .for_each(|other| queue.push(other))
-    }
-
-    Ok(count)
+    Ok(copy_counts(cards)?.into_iter().sum())
 }
 
 #[cfg(test)]
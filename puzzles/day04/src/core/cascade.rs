@@ -0,0 +1,55 @@
+use anyhow::ensure;
+
+use super::Card;
+
+// Cards only ever win copies of the cards after them, so by the time a card is reached, every
+// copy of it that will ever be won has already been counted. Each card's copies then win another
+// round of copies of the cards that follow, all at once.
+pub fn copy_counts(cards: &[Card]) -> anyhow::Result<Vec<usize>> {
+    let mut counts = vec![1; cards.len()];
+
+    for (i, card) in cards.iter().enumerate() {
+        let match_count = card.matching_numbers().count();
+        let remaining = cards.len() - i - 1;
+
+        ensure!(
+            match_count <= remaining,
+            "card number {} wins copies of the next {} cards, but there are only {} after it",
+            i + 1,
+            match_count,
+            remaining
+        );
+
+        let copies = counts[i];
+
+        for count in &mut counts[i + 1..=i + match_count] {
+            *count += copies;
+        }
+    }
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../../input-test");
+
+    fn parse(input: &str) -> Vec<Card> {
+        input.lines().map(|s| Card::try_from(s).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_copy_counts() -> anyhow::Result<()> {
+        assert_eq!(copy_counts(&parse(INPUT))?, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(copy_counts(&[])?, Vec::<usize>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_past_the_end() {
+        let cards = parse("Card 1: 1 2 | 1 3\nCard 2: 1 2 | 1 2");
+        assert!(copy_counts(&cards).is_err());
+    }
+}
@@ -4,3 +4,6 @@ pub type Score = u64;
 
 pub mod card;
 pub use card::*;
+
+pub mod cascade;
+pub use cascade::*;
@@ -1,8 +1,10 @@
-use anyhow::{anyhow, Context};
+use anyhow::Context;
 
 use aoc::Input;
 
-use crate::core::{Bid, Card, Hand};
+use std::rc::Rc;
+
+use crate::core::{Bid, Card, Hand, Rules};
 
 type Play = (Hand, Bid);
 
@@ -10,57 +12,40 @@ type Parsed = Vec<Play>;
 pub type Parsed1 = Parsed;
 pub type Parsed2 = Parsed;
 
-fn parse_card(c: char, j_card: Card) -> anyhow::Result<Card> {
-    match c {
-        '2' => Ok(Card::Two),
-        '3' => Ok(Card::Three),
-        '4' => Ok(Card::Four),
-        '5' => Ok(Card::Five),
-        '6' => Ok(Card::Six),
-        '7' => Ok(Card::Seven),
-        '8' => Ok(Card::Eight),
-        '9' => Ok(Card::Nine),
-        'T' => Ok(Card::Ten),
-        'J' => Ok(j_card),
-        'Q' => Ok(Card::Queen),
-        'K' => Ok(Card::King),
-        'A' => Ok(Card::Ace),
-        _ => Err(anyhow!("invalid card: {:?}", c)),
-    }
-}
-
-fn parse_play(s: &str, j_card: Card) -> anyhow::Result<Play> {
+fn parse_play(s: &str, rules: &Rc<Rules>) -> anyhow::Result<Play> {
     let (left, right) = s
         .split_once(|c: char| c.is_whitespace())
         .context("expected hand/bid to be delimited by whitespace")?;
 
-    let hand = left
+    let cards = left
         .chars()
         .enumerate()
-        .map(|(i, c)| parse_card(c, j_card).with_context(|| format!("card number {}", i + 1)))
-        .collect::<Result<Vec<_>, _>>()?
-        .try_into()
-        .context("hand")?;
+        .map(|(i, c)| Card::try_from(c).with_context(|| format!("card number {}", i + 1)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let hand = Hand::new(cards, Rc::clone(rules)).context("hand")?;
 
     let bid = right.parse().context("bid")?;
 
     Ok((hand, bid))
 }
 
-fn parse(input: Input, j_card: Card) -> anyhow::Result<Parsed> {
+pub fn parse_with(input: Input, rules: Rules) -> anyhow::Result<Parsed> {
+    let rules = Rc::new(rules);
+
     input
         .lines()
         .enumerate()
-        .map(|(i, s)| parse_play(s, j_card).with_context(|| format!("play number {}", i + 1)))
+        .map(|(i, s)| parse_play(s, &rules).with_context(|| format!("play number {}", i + 1)))
         .collect::<Result<Vec<_>, _>>()
 }
 
 pub fn parse1(input: Input) -> anyhow::Result<Parsed1> {
-    parse(input, Card::Jack)
+    parse_with(input, Rules::standard())
 }
 
 pub fn parse2(input: Input) -> anyhow::Result<Parsed2> {
-    parse(input, Card::Joker)
+    parse_with(input, Rules::jokers())
 }
 
 #[cfg(test)]
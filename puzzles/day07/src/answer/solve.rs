@@ -1,12 +1,14 @@
+use crate::core::{Bid, Hand};
+
 use super::{Parsed1, Parsed2};
 
 type Solution = usize;
 pub type Solution1 = Solution;
 pub type Solution2 = Solution;
 
-fn solve(plays: &Parsed1) -> anyhow::Result<Solution> {
-    let mut plays = plays.clone();
-    plays.sort_by(|a, b| a.0.cmp(&b.0).reverse());
+pub fn total_winnings(plays: &[(Hand, Bid)]) -> anyhow::Result<Solution> {
+    let mut plays = plays.to_vec();
+    plays.sort_by_key(|(hand, _)| hand.key());
 
     Ok(plays
         .into_iter()
@@ -16,18 +18,21 @@ fn solve(plays: &Parsed1) -> anyhow::Result<Solution> {
 }
 
 pub fn solve1(plays: &Parsed1) -> anyhow::Result<Solution1> {
-    solve(plays)
+    total_winnings(plays)
 }
 
 pub fn solve2(plays: &Parsed2) -> anyhow::Result<Solution2> {
-    solve(plays)
+    total_winnings(plays)
 }
 
 #[cfg(test)]
 mod tests {
     use aoc::Input;
 
-    use crate::answer::{parse1, parse2};
+    use std::rc::Rc;
+
+    use crate::answer::{parse1, parse2, parse_with};
+    use crate::core::{Card, HandKind, Rules, TieBreak, MAX_HAND_LEN};
 
     use super::*;
 
@@ -44,4 +49,68 @@ mod tests {
         assert_eq!(solve2(&parse2(INPUT)?)?, 5905);
         Ok(())
     }
+
+    #[test]
+    fn test_rules() -> anyhow::Result<()> {
+        let no_wild_jack_low = Rules {
+            wild: None,
+            ..Rules::jokers()
+        };
+        assert_eq!(total_winnings(&parse_with(INPUT, no_wild_jack_low)?)?, 6440);
+
+        let right_to_left = Rules {
+            tie_break: TieBreak::RightToLeft,
+            ..Rules::standard()
+        };
+        assert_eq!(total_winnings(&parse_with(INPUT, right_to_left)?)?, 6632);
+
+        let six_cards = Rules {
+            hand_len: 6,
+            ..Rules::standard()
+        };
+        let input = "AAAKKK 1\nAAAAKQ 2\n22334Q 3\n";
+        assert_eq!(total_winnings(&parse_with(input, six_cards.clone())?)?, 11);
+        assert!(parse_with(INPUT, six_cards).is_err());
+
+        let no_jacks = Rules {
+            ranking: Rules::standard()
+                .ranking
+                .into_iter()
+                .filter(|&card| card != Card::Jack)
+                .collect(),
+            ..Rules::standard()
+        };
+        assert!(parse_with(INPUT, no_jacks).is_err());
+
+        let too_long = Rules {
+            hand_len: MAX_HAND_LEN + 1,
+            ..Rules::standard()
+        };
+        assert!(Hand::new(vec![Card::Ace; MAX_HAND_LEN + 1], Rc::new(too_long)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_kind() -> anyhow::Result<()> {
+        let kinds =
+            |plays: Parsed1| -> Vec<_> { plays.into_iter().map(|(hand, _)| hand.kind()).collect() };
+
+        use HandKind::*;
+
+        assert_eq!(
+            kinds(parse1(INPUT)?),
+            [OnePair, ThreeKind, TwoPair, TwoPair, ThreeKind]
+        );
+        assert_eq!(
+            kinds(parse2(INPUT)?),
+            [OnePair, FourKind, TwoPair, FourKind, FourKind]
+        );
+        assert_eq!(
+            kinds(parse2("JJJJJ 1\nJ2233 2\nJJ234 3\n")?),
+            [FiveKind, FullHouse, ThreeKind]
+        );
+
+        Ok(())
+    }
 }
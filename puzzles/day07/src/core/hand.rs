@@ -1,32 +1,27 @@
-use anyhow::ensure;
+use anyhow::{ensure, Context};
 
 use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::convert::TryFrom;
+use std::rc::Rc;
+
+use super::{Card, Rules, TieBreak, CARD_COUNT};
 
 type Inner = Vec<Card>;
 
-pub const HAND_LEN: usize = 5;
+// The sizes of the groups of matching cards in a hand, largest first, with any wild cards added
+// to the largest group. Comparing these from the start is the same as comparing hand kinds.
+pub type Signature = [u8; CARD_COUNT];
+
+// Each card's rank takes up four bits of the sort key, under the bits for the hand kind.
+const RANK_BITS: usize = 4;
+pub const MAX_HAND_LEN: usize = (u128::BITS as usize - 8) / RANK_BITS;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Hand(Inner);
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub enum Card {
-    Joker,
-    Two,
-    Three,
-    Four,
-    Five,
-    Six,
-    Seven,
-    Eight,
-    Nine,
-    Ten,
-    Jack,
-    Queen,
-    King,
-    Ace,
+pub struct Hand {
+    cards: Inner,
+    rules: Rc<Rules>,
+    signature: Signature,
+    kind: HandKind,
+    key: u128,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -40,68 +35,128 @@ pub enum HandKind {
     HighCard,
 }
 
-impl Hand {
-    pub fn kind(&self) -> HandKind {
-        let mut counts: HashMap<&Card, usize> =
-            self.0.iter().fold(HashMap::new(), |mut counts, card| {
-                *counts.entry(card).or_default() += 1;
-                counts
-            });
-
-        // If this hand contains jokers, add that count to the largest group.
-        if let Some(joker_count) = counts.remove(&Card::Joker) {
-            let card = counts
-                .iter()
-                .max_by_key(|&(_, count)| count)
-                .map(|(card, _)| card)
-                .unwrap_or(&&Card::Ace);
-
-            *counts.entry(card).or_default() += joker_count;
-        }
-
-        match counts.len() {
-            1 => HandKind::FiveKind,
-            2 => match counts.into_values().next().unwrap() {
-                1 | 4 => HandKind::FourKind,
-                2 | 3 => HandKind::FullHouse,
-                _ => unreachable!(),
-            },
-            3 => match counts.into_values().find(|&count| count == 3) {
-                Some(_) => HandKind::ThreeKind,
-                None => HandKind::TwoPair,
-            },
-            4 => HandKind::OnePair,
-            5 => HandKind::HighCard,
-            _ => unreachable!(),
+impl HandKind {
+    // Hands longer than five cards can have bigger groups, but they still count as the best kind
+    // they could make with five of their cards.
+    pub fn from_signature(signature: &Signature) -> Self {
+        match (signature[0], signature[1]) {
+            (5.., _) => Self::FiveKind,
+            (4, _) => Self::FourKind,
+            (3, 2..) => Self::FullHouse,
+            (3, _) => Self::ThreeKind,
+            (2, 2..) => Self::TwoPair,
+            (2, _) => Self::OnePair,
+            _ => Self::HighCard,
         }
     }
-}
 
-impl Ord for Hand {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.kind()
-            .cmp(&other.kind())
-            .then_with(|| self.0.cmp(&other.0).reverse())
+    fn strength(&self) -> u128 {
+        Self::HighCard as u128 - *self as u128
     }
 }
 
-impl PartialOrd for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+fn count_signature(cards: &[Card], rules: &Rules) -> Signature {
+    let mut counts = [0; CARD_COUNT];
+
+    for &card in cards {
+        counts[card as usize] += 1;
     }
+
+    let wild_count = rules
+        .wild
+        .map(|wild| std::mem::take(&mut counts[wild as usize]))
+        .unwrap_or_default();
+
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    counts[0] += wild_count;
+
+    counts
 }
 
-impl TryFrom<Inner> for Hand {
-    type Error = anyhow::Error;
+impl Hand {
+    pub fn new(cards: Inner, rules: Rc<Rules>) -> anyhow::Result<Self> {
+        ensure!(
+            rules.hand_len <= MAX_HAND_LEN,
+            "expected hands to have at most {} cards, but the rules call for {}",
+            MAX_HAND_LEN,
+            rules.hand_len
+        );
+
+        ensure!(
+            rules.ranking.len() <= 1 << RANK_BITS,
+            "expected at most {} ranked cards, but there were {}",
+            1 << RANK_BITS,
+            rules.ranking.len()
+        );
 
-    fn try_from(cards: Inner) -> Result<Self, Self::Error> {
         ensure!(
-            cards.len() == HAND_LEN,
+            cards.len() == rules.hand_len,
             "expected there to be {} cards, but there were {}",
-            HAND_LEN,
+            rules.hand_len,
             cards.len()
         );
 
-        Ok(Self(cards))
+        let mut ranks = cards
+            .iter()
+            .enumerate()
+            .map(|(i, &card)| {
+                rules
+                    .rank(card)
+                    .with_context(|| format!("card number {} ({:?}) is not ranked", i + 1, card))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let signature = count_signature(&cards, &rules);
+        let kind = HandKind::from_signature(&signature);
+
+        if rules.tie_break == TieBreak::RightToLeft {
+            ranks.reverse();
+        }
+
+        // Stronger hands get bigger keys, with the kind taking priority over the card ranks.
+        let key = ranks.into_iter().fold(kind.strength(), |key, rank| {
+            (key << RANK_BITS) | rank as u128
+        });
+
+        Ok(Self {
+            cards,
+            rules,
+            signature,
+            kind,
+            key,
+        })
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    pub fn kind(&self) -> HandKind {
+        self.kind
+    }
+
+    pub fn key(&self) -> u128 {
+        self.key
+    }
+}
+
+// Stronger hands come first, and hands are only comparable under the same rules.
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key).reverse()
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
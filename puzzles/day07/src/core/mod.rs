@@ -1,4 +1,10 @@
 pub type Bid = usize;
 
+pub mod card;
+pub use card::*;
+
+pub mod rules;
+pub use rules::*;
+
 pub mod hand;
 pub use hand::*;
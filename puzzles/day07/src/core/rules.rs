@@ -0,0 +1,47 @@
+use super::Card;
+
+use Card::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    LeftToRight,
+    RightToLeft,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    // Every card that can be played, from weakest to strongest.
+    pub ranking: Vec<Card>,
+    // The card that can stand in for any other when deciding what kind of hand it is.
+    pub wild: Option<Card>,
+    pub hand_len: usize,
+    // The order in which the cards of two hands of the same kind are compared.
+    pub tie_break: TieBreak,
+}
+
+impl Rules {
+    pub fn standard() -> Self {
+        Self {
+            ranking: vec![
+                Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace,
+            ],
+            wild: None,
+            hand_len: 5,
+            tie_break: TieBreak::LeftToRight,
+        }
+    }
+
+    pub fn jokers() -> Self {
+        Self {
+            ranking: vec![
+                Jack, Two, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Queen, King, Ace,
+            ],
+            wild: Some(Jack),
+            ..Self::standard()
+        }
+    }
+
+    pub fn rank(&self, card: Card) -> Option<usize> {
+        self.ranking.iter().position(|&other| other == card)
+    }
+}
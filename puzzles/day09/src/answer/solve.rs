@@ -1,3 +1,5 @@
+use anyhow::Context;
+
 use crate::core::Value;
 
 use super::{Parsed1, Parsed2};
@@ -7,17 +9,21 @@ pub type Solution1 = Solution;
 pub type Solution2 = Solution;
 
 pub fn solve1(histories: &Parsed1) -> anyhow::Result<Solution1> {
-    Ok(histories
+    histories
         .iter()
-        .map(|history| history.extrapolate_next().next().unwrap())
-        .sum())
+        .map(|history| {
+            history
+                .value_at(history.len() as isize)
+                .context("next value is too large")
+        })
+        .sum()
 }
 
 pub fn solve2(histories: &Parsed2) -> anyhow::Result<Solution2> {
-    Ok(histories
+    histories
         .iter()
-        .map(|history| history.extrapolate_prev().next().unwrap())
-        .sum())
+        .map(|history| history.value_at(-1).context("previous value is too large"))
+        .sum()
 }
 
 #[cfg(test)]
@@ -1,13 +1,16 @@
-use anyhow::Context;
+use anyhow::{ensure, Context};
 
 use std::convert::TryFrom;
 
-use super::Value;
+use super::{Polynomial, Value};
 
 type Inner = Vec<Value>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct History(Inner);
+pub struct History {
+    values: Inner,
+    polynomial: Polynomial,
+}
 
 fn differences(values: Vec<Value>) -> impl Iterator<Item = Vec<Value>> {
     let mut current = values;
@@ -26,41 +29,66 @@ fn differences(values: Vec<Value>) -> impl Iterator<Item = Vec<Value>> {
     })
 }
 
-fn extrapolate(values: Vec<Value>) -> impl Iterator<Item = Value> {
-    let mut current = values.last().cloned().unwrap();
+impl History {
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
 
-    let mut latest_values: Vec<_> = differences(values)
-        .map(|mut values| values.pop().unwrap())
-        .collect();
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
 
-    latest_values.reverse();
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
 
-    std::iter::from_fn(move || {
-        for i in 0..latest_values.len() - 1 {
-            latest_values[i + 1] += latest_values[i];
-        }
+    pub fn polynomial(&self) -> &Polynomial {
+        &self.polynomial
+    }
 
-        current += latest_values.last().cloned().unwrap();
+    pub fn degree(&self) -> usize {
+        self.polynomial.degree()
+    }
 
-        Some(current)
-    })
-}
+    // The first value is at index 0, so the next one after the last is at the history's length
+    // and the one before the first is at -1.
+    pub fn value_at(&self, index: isize) -> Option<Value> {
+        self.polynomial.evaluate(index)
+    }
 
-impl History {
-    pub fn extrapolate_next(&self) -> impl Iterator<Item = Value> {
-        extrapolate(self.0.clone())
+    pub fn extrapolate_next(&self) -> impl Iterator<Item = Value> + '_ {
+        (self.len() as isize..).map_while(|index| self.value_at(index))
     }
 
-    pub fn extrapolate_prev(&self) -> impl Iterator<Item = Value> {
-        let mut values = self.0.clone();
-        values.reverse();
-        extrapolate(values)
+    pub fn extrapolate_prev(&self) -> impl Iterator<Item = Value> + '_ {
+        (1..)
+            .map(|offset: isize| -offset)
+            .map_while(|index| self.value_at(index))
     }
 }
 
-impl From<Inner> for History {
-    fn from(values: Inner) -> Self {
-        Self(values)
+impl TryFrom<Inner> for History {
+    type Error = anyhow::Error;
+
+    fn try_from(values: Inner) -> Result<Self, Self::Error> {
+        let mut rows = vec![values.clone()];
+        rows.extend(differences(values.clone()));
+
+        // Differences only stop once they are all zeros or run out, and running out means there
+        // were not enough values to tell what polynomial they came from.
+        let last = rows.pop().unwrap();
+        ensure!(
+            !last.is_empty(),
+            "differences of the {} values never settled to all zeros",
+            values.len()
+        );
+
+        let coefficients = rows.iter().map(|row| row[0]).collect();
+
+        Ok(Self {
+            values,
+            polynomial: Polynomial::new(coefficients),
+        })
     }
 }
 
@@ -68,12 +96,12 @@ impl TryFrom<&str> for History {
     type Error = anyhow::Error;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
-        Ok(input
+        input
             .split_whitespace()
             .enumerate()
             .map(|(i, s)| s.parse().with_context(|| format!("value number {}", i + 1)))
             .collect::<Result<Vec<_>, _>>()?
-            .into())
+            .try_into()
     }
 }
 
@@ -108,7 +136,8 @@ mod tests {
         macro_rules! test {
             ($input:expr, $expected:expr) => {
                 assert_eq!(
-                    History::from(Vec::from($input))
+                    History::try_from(Vec::from($input))
+                        .unwrap()
                         .extrapolate_next()
                         .take($expected.len())
                         .collect::<Vec<_>>(),
@@ -129,7 +158,8 @@ mod tests {
         macro_rules! test {
             ($input:expr, $expected:expr) => {
                 assert_eq!(
-                    History::from(Vec::from($input))
+                    History::try_from(Vec::from($input))
+                        .unwrap()
                         .extrapolate_prev()
                         .take($expected.len())
                         .collect::<Vec<_>>(),
@@ -144,4 +174,37 @@ mod tests {
 
         test!([10, 13, 16, 21, 30, 45], [5, -4, -19, -42]);
     }
+
+    #[test]
+    fn test_history_value_at() -> anyhow::Result<()> {
+        let history = History::try_from(vec![10, 13, 16, 21, 30, 45])?;
+        assert_eq!(history.degree(), 3);
+        assert_eq!(history.polynomial().coefficients(), [10, 3, 0, 2]);
+
+        for (i, &value) in history.values().iter().enumerate() {
+            assert_eq!(history.value_at(i as isize), Some(value));
+        }
+
+        assert_eq!(history.value_at(-4), Some(-42));
+        assert_eq!(history.value_at(1_000), Some(332_337_010));
+        assert_eq!(history.value_at(isize::MAX), None);
+
+        let history = History::try_from(vec![7, 7, 7])?;
+        assert_eq!(history.degree(), 0);
+        assert_eq!(history.value_at(-1_000_000), Some(7));
+
+        let history = History::try_from(vec![0, 0])?;
+        assert_eq!(history.degree(), 0);
+        assert_eq!(history.value_at(5), Some(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_not_polynomial() {
+        assert!(History::try_from(vec![]).is_err());
+        assert!(History::try_from(vec![5]).is_err());
+        assert!(History::try_from(vec![1, 2, 4, 8, 16]).is_err());
+        assert!(History::try_from(vec![1, 2, 4, 8, 15]).is_ok());
+    }
 }
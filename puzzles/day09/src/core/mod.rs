@@ -1,4 +1,7 @@
 pub type Value = isize;
 
+pub mod polynomial;
+pub use polynomial::*;
+
 pub mod history;
 pub use history::*;
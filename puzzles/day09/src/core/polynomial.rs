@@ -0,0 +1,49 @@
+use super::Value;
+
+// A polynomial in Newton's forward difference form, where each coefficient is the first value of
+// a row in the difference table:
+//
+//     f(x) = Δ⁰ * C(x, 0) + Δ¹ * C(x, 1) + Δ² * C(x, 2) + ...
+//
+// Every C(x, k) is an integer for any integer x, including negative ones, so evaluating it never
+// leaves a remainder.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Polynomial {
+    coefficients: Vec<Value>,
+}
+
+impl Polynomial {
+    pub fn new(coefficients: Vec<Value>) -> Self {
+        Self { coefficients }
+    }
+
+    pub fn coefficients(&self) -> &[Value] {
+        &self.coefficients
+    }
+
+    // The zero polynomial is treated as a constant.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len().saturating_sub(1)
+    }
+
+    // Returns nothing if the value does not fit.
+    pub fn evaluate(&self, x: isize) -> Option<Value> {
+        let x = x as i128;
+
+        let mut total: i128 = 0;
+        let mut binomial: i128 = 1;
+
+        for (k, &coefficient) in self.coefficients.iter().enumerate() {
+            if k > 0 {
+                // The product of k consecutive integers is always divisible by k!, so this stays
+                // exact.
+                let k = k as i128;
+                binomial = binomial.checked_mul(x - k + 1)? / k;
+            }
+
+            total = total.checked_add(binomial.checked_mul(coefficient as i128)?)?;
+        }
+
+        total.try_into().ok()
+    }
+}
@@ -22,6 +22,8 @@ pub fn parse2(input: Input) -> anyhow::Result<Parsed2> {
 mod tests {
     use aoc::Input;
 
+    use crate::core::Tile;
+
     use super::*;
 
     const INPUT1A: Input = include_str!("../../input-test1a");
@@ -45,4 +47,42 @@ mod tests {
         dbg!(parse(INPUT6)?);
         Ok(())
     }
+
+    #[test]
+    fn test_invalid() {
+        // The start tile only connects to one neighbor.
+        assert!(parse(".....\n.S-7.\n...|.\n.L-J.\n.....\n").is_err());
+
+        // The start tile connects to two separate loops.
+        assert!(parse("F-7..\n|.|..\nL-S-7\n..|.|\n..L-J\n").is_err());
+
+        // The start tile connects to three neighbors, but none of them form a loop.
+        assert!(parse(".....\n-S-7.\n.|.|.\n.L-|.\n.....\n").is_err());
+
+        // The loop is broken by a pipe that connects back the wrong way.
+        assert!(parse(".....\n.S-7.\n.|.|.\n.L-|.\n.....\n").is_err());
+
+        // The loop leads off the edge of the field.
+        assert!(parse("S-7\n|.|\nL-L\n").is_err());
+
+        // There is more than one start tile.
+        assert!(parse(".....\n.S-7.\n.|.|.\n.L-S.\n.....\n").is_err());
+
+        assert!(parse("").is_err());
+    }
+
+    #[test]
+    fn test_start_inference() -> anyhow::Result<()> {
+        // Only two of the three neighbors connected to the start tile form a loop.
+        let field = parse(".....\n-S-7.\n.|.|.\n.L-J.\n.....\n")?;
+        assert_eq!(field.grid[field.start], Tile::TopLeft);
+        assert_eq!(field.loop_path().len(), 8);
+
+        // Every neighbor connects to the start tile, but only one pair forms a loop.
+        let field = parse("F-7..\n|.|..\nL-S-.\n..|..\n.....\n")?;
+        assert_eq!(field.grid[field.start], Tile::BottomRight);
+        assert_eq!(field.loop_path().len(), 8);
+
+        Ok(())
+    }
 }
@@ -14,7 +14,7 @@ pub fn solve1(field: &Parsed1) -> anyhow::Result<Solution1> {
 }
 
 pub fn solve2(field: &Parsed2) -> anyhow::Result<Solution2> {
-    let loop_locations: Vec<_> = field.loop_path();
+    let loop_locations = field.loop_path();
 
     let loop_vertices: Vec<_> = loop_locations
         .iter()
@@ -44,6 +44,8 @@ pub fn solve2(field: &Parsed2) -> anyhow::Result<Solution2> {
 mod tests {
     use aoc::Input;
 
+    use std::collections::HashSet;
+
     use crate::answer::{parse1, parse2};
 
     use super::*;
@@ -78,4 +80,45 @@ mod tests {
         assert_eq!(solve2(&parse2(INPUT6)?)?, 10);
         Ok(())
     }
+
+    #[test]
+    fn test_enclosed() -> anyhow::Result<()> {
+        for input in [
+            INPUT1A, INPUT1B, INPUT2A, INPUT2B, INPUT3, INPUT4, INPUT5, INPUT6,
+        ] {
+            let field = parse2(input)?;
+            assert_eq!(field.enclosed().len(), solve2(&field)?);
+        }
+
+        assert_eq!(
+            parse2(INPUT3)?.enclosed(),
+            HashSet::from([(6, 2), (6, 3), (6, 7), (6, 8)])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_cleaned() -> anyhow::Result<()> {
+        let field = parse2(INPUT1B)?.cleaned();
+        let (height, width) = field.grid.size();
+
+        let pipes = (0..height)
+            .flat_map(|i| (0..width).map(move |j| (i, j)))
+            .filter(|&location| field.grid[location] != Tile::Empty)
+            .count();
+
+        assert_eq!(pipes, field.loop_path().len());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_display() -> anyhow::Result<()> {
+        assert_eq!(
+            format!("{}", parse2(INPUT1B)?),
+            concat!("░░░░░\n", "░┌─┐░\n", "░│▓│░\n", "░└─┘░\n", "░░░░░\n",)
+        );
+        Ok(())
+    }
 }
@@ -1,42 +1,113 @@
 use anyhow::{bail, ensure, Context};
 
-use std::collections::VecDeque;
+use std::collections::HashSet;
 use std::convert::TryFrom;
 
-use super::{Grid, Location, Tile, DIRECTIONS};
+use super::{Direction, Grid, Location, Tile, DIRECTIONS};
 
 #[derive(Debug, Clone)]
 pub struct Field {
     pub start: Location,
     pub grid: Grid<Tile>,
+    pub path: Vec<Location>,
 }
 
-impl Field {
-    pub fn loop_path(&self) -> Vec<Location> {
-        let mut path = Vec::new();
+fn describe((row, column): Location) -> String {
+    format!("row number {}, column number {}", row + 1, column + 1)
+}
+
+fn trace_loop(grid: &Grid<Tile>, start: Location) -> anyhow::Result<Vec<Location>> {
+    let mut path = Vec::new();
+
+    let mut location = start;
+    let mut direction = grid[start]
+        .edges()
+        .next()
+        .context("start tile has no edges")?;
+
+    loop {
+        path.push(location);
+
+        let (adjacent_location, adjacent_tile) =
+            grid.neighbor(location, direction).with_context(|| {
+                format!(
+                    "pipe at {} leads {:?} off the edge of the field",
+                    describe(location),
+                    direction
+                )
+            })?;
+
+        ensure!(
+            adjacent_tile.has_edge(direction.opposite()),
+            "pipe at {} leads {:?} to an unconnected tile at {}",
+            describe(location),
+            direction,
+            describe(adjacent_location)
+        );
+
+        if adjacent_location == start {
+            break;
+        }
 
-        let direction = self.grid.get(self.start).unwrap().edges().next().unwrap();
-        let mut frontier = VecDeque::from([(self.start, direction)]);
+        location = adjacent_location;
+        direction = adjacent_tile
+            .edges()
+            .find(|&other_direction| other_direction != direction.opposite())
+            .with_context(|| format!("pipe at {} is a dead end", describe(location)))?;
+    }
 
-        while let Some((location, direction)) = frontier.pop_front() {
-            path.push(location);
+    Ok(path)
+}
 
-            let (adjacent_location, adjacent_tile) =
-                self.grid.neighbor(location, direction).unwrap();
+impl Field {
+    pub fn loop_path(&self) -> &[Location] {
+        &self.path
+    }
 
-            if adjacent_location == self.start {
-                break;
+    pub fn enclosed(&self) -> HashSet<Location> {
+        let on_loop: HashSet<_> = self.path.iter().copied().collect();
+        let (height, width) = self.grid.size();
+
+        let mut enclosed = HashSet::new();
+
+        // Scanning each row from the left, every loop tile that connects north is a boundary
+        // crossing, so the tiles after an odd number of crossings are inside the loop.
+        for i in 0..height {
+            let mut inside = false;
+
+            for j in 0..width {
+                if on_loop.contains(&(i, j)) {
+                    if self.grid[(i, j)].has_edge(Direction::North) {
+                        inside = !inside;
+                    }
+                } else if inside {
+                    enclosed.insert((i, j));
+                }
             }
+        }
+
+        enclosed
+    }
+
+    pub fn cleaned(&self) -> Self {
+        let on_loop: HashSet<_> = self.path.iter().copied().collect();
+        let (height, width) = self.grid.size();
 
-            let next_direction = adjacent_tile
-                .edges()
-                .find(|&other_direction| other_direction.opposite() != direction)
-                .unwrap();
+        let mut grid = self.grid.clone();
 
-            frontier.push_back((adjacent_location, next_direction));
+        for i in 0..height {
+            for j in 0..width {
+                if !on_loop.contains(&(i, j)) {
+                    grid[(i, j)] = Tile::Empty;
+                }
+            }
         }
 
-        path
+        Self {
+            start: self.start,
+            grid,
+            path: self.path.clone(),
+        }
     }
 }
 
@@ -65,29 +136,105 @@ impl TryFrom<&str> for Field {
         for (i, line) in lines {
             for (j, c) in line.chars().enumerate() {
                 values.push(if c == 'S' {
+                    ensure!(
+                        start.is_none(),
+                        "found a second start tile at {}",
+                        describe((i, j))
+                    );
                     start = Some((i, j));
                     Tile::Empty // Wait until other tiles are in place.
                 } else {
-                    Tile::try_from(c)
-                        .with_context(|| format!("row number {}, column number {}", i + 1, j + 1))?
+                    Tile::try_from(c).with_context(|| describe((i, j)))?
                 })
             }
         }
 
         let mut grid = Grid::from_iter(height, width, values);
 
-        if let Some(start) = start {
-            grid[start] = DIRECTIONS
-                .map(|direction| {
-                    grid.neighbor(start, direction)
-                        .map(|(_, tile)| tile.has_edge(direction.opposite()))
-                        .unwrap_or_default()
-                })
-                .try_into()?;
+        let start = start.context("no start tile found")?;
+
+        let candidates: Vec<_> = DIRECTIONS
+            .into_iter()
+            .filter(|&direction| {
+                grid.neighbor(start, direction)
+                    .map(|(_, tile)| tile.has_edge(direction.opposite()))
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        ensure!(
+            candidates.len() >= 2,
+            "expected start tile to connect to at least 2 neighbors, but it connected to {}",
+            candidates.len()
+        );
+
+        let mut loops = Vec::new();
+        let mut errors = Vec::new();
+
+        // Any two of the connecting neighbors could be the ends of the loop, so try them all.
+        for (i, &a) in candidates.iter().enumerate() {
+            for &b in &candidates[i + 1..] {
+                let tile: Tile = DIRECTIONS
+                    .map(|direction| direction == a || direction == b)
+                    .try_into()?;
+
+                grid[start] = tile;
+
+                match trace_loop(&grid, start) {
+                    Ok(path) => loops.push((tile, path)),
+                    Err(error) => errors.push(error),
+                }
+            }
+        }
+
+        if loops.is_empty() {
+            if errors.len() == 1 {
+                return Err(errors
+                    .pop()
+                    .unwrap()
+                    .context("start tile is not part of a loop"));
+            }
 
-            Ok(Self { start, grid })
-        } else {
-            bail!("no start tile found");
+            bail!(
+                "start tile is not part of a loop through any of its {} connected neighbors",
+                candidates.len()
+            );
         }
+
+        ensure!(
+            loops.len() == 1,
+            "ambiguous start tile, which could be any of: {:?}",
+            loops.iter().map(|(tile, _)| tile).collect::<Vec<_>>()
+        );
+
+        let (tile, path) = loops.pop().unwrap();
+        grid[start] = tile;
+
+        Ok(Self { start, grid, path })
+    }
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let on_loop: HashSet<_> = self.path.iter().copied().collect();
+        let enclosed = self.enclosed();
+        let (height, width) = self.grid.size();
+
+        for i in 0..height {
+            for j in 0..width {
+                let location = (i, j);
+                let c = if on_loop.contains(&location) {
+                    self.grid[location].box_char()
+                } else if enclosed.contains(&location) {
+                    '▓'
+                } else {
+                    '░'
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
     }
 }
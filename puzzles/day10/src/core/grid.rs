@@ -33,15 +33,15 @@ impl<T> Grid<T> {
         self.0.get_mut(location)
     }
 
-    pub fn row_iter(&self) -> RowIter<T, Dyn, Dyn, VecStorage<T, Dyn, Dyn>> {
+    pub fn row_iter(&self) -> RowIter<'_, T, Dyn, Dyn, VecStorage<T, Dyn, Dyn>> {
         self.0.row_iter()
     }
 
-    pub fn column_iter(&self) -> ColumnIter<T, Dyn, Dyn, VecStorage<T, Dyn, Dyn>> {
+    pub fn column_iter(&self) -> ColumnIter<'_, T, Dyn, Dyn, VecStorage<T, Dyn, Dyn>> {
         self.0.column_iter()
     }
 
-    pub fn neighbor(&self, location: Location, direction: Direction) -> Option<Cell<T>> {
+    pub fn neighbor(&self, location: Location, direction: Direction) -> Option<Cell<'_, T>> {
         neighbor(location, direction)
             .and_then(|adjacent| self.0.get(adjacent).map(|value| (adjacent, value)))
     }
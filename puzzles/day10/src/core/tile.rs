@@ -38,6 +38,18 @@ impl Tile {
         self.bits() & direction.bits() != 0
     }
 
+    pub fn box_char(&self) -> char {
+        match self {
+            Self::Empty => ' ',
+            Self::TopLeft => '┌',
+            Self::TopRight => '┐',
+            Self::Vertical => '│',
+            Self::Horizontal => '─',
+            Self::BottomLeft => '└',
+            Self::BottomRight => '┘',
+        }
+    }
+
     pub fn edges(&self) -> impl Iterator<Item = Direction> + '_ {
         DIRECTIONS
             .into_iter()
@@ -57,7 +69,7 @@ impl TryFrom<Edges> for Tile {
             [true, true, false, false] => Ok(Self::BottomLeft),
             [true, false, false, true] => Ok(Self::BottomRight),
             _ => Err(anyhow!(
-                "invalid tile edges (north, west, east, south): {:?}",
+                "invalid tile edges (north, east, south, west): {:?}",
                 edges
             )),
         }
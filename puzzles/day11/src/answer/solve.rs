@@ -1,68 +1,17 @@
-use itertools::Itertools;
-
-use std::collections::HashSet;
-
-use crate::core::{Image, Location};
+use crate::core::{total_distance, Distance};
 
 use super::{Parsed1, Parsed2};
 
-type Solution = usize;
+type Solution = Distance;
 pub type Solution1 = Solution;
 pub type Solution2 = Solution;
 
-fn galaxy_distances(
-    image: &Image,
-    multiplier: usize,
-) -> impl Iterator<Item = ((Location, Location), usize)> + '_ {
-    let top = image.iter().min_by_key(|(row, _)| row).unwrap().0;
-    let bottom = image.iter().max_by_key(|(row, _)| row).unwrap().0;
-    let left = image.iter().min_by_key(|(_, column)| column).unwrap().1;
-    let right = image.iter().max_by_key(|(_, column)| column).unwrap().1;
-
-    let empty_rows: HashSet<_> = (top..=bottom)
-        .filter(|&row| (left..=right).all(|column| !image.contains(&(row, column))))
-        .collect();
-
-    let empty_columns: HashSet<_> = (left..=right)
-        .filter(|&column| (top..=bottom).all(|row| !image.contains(&(row, column))))
-        .collect();
-
-    image.iter().combinations(2).map(move |mut pair| {
-        let (row1, column1) = pair.pop().copied().unwrap();
-        let (row2, column2) = pair.pop().copied().unwrap();
-
-        let top = row1.min(row2);
-        let bottom = row1.max(row2);
-        let left = column1.min(column2);
-        let right = column1.max(column2);
-
-        let num_empty_rows = (top..=bottom)
-            .filter(|row| empty_rows.contains(row))
-            .count();
-
-        let num_empty_columns = (left..=right)
-            .filter(|column| empty_columns.contains(column))
-            .count();
-
-        let distance =
-            (bottom - top) + (right - left) + (num_empty_rows + num_empty_columns) * multiplier;
-
-        (((top, left), (bottom, right)), distance)
-    })
-}
-
-fn solve(image: &Image, multiplier: usize) -> Solution {
-    galaxy_distances(image, multiplier)
-        .map(|(_, distance)| distance)
-        .sum()
-}
-
 pub fn solve1(image: &Parsed1) -> anyhow::Result<Solution1> {
-    Ok(solve(image, 1))
+    Ok(total_distance(image, 2))
 }
 
 pub fn solve2(image: &Parsed2) -> anyhow::Result<Solution2> {
-    Ok(solve(image, 999_999))
+    Ok(total_distance(image, 1_000_000))
 }
 
 #[cfg(test)]
@@ -70,6 +19,7 @@ mod tests {
     use aoc::Input;
 
     use crate::answer::parse;
+    use crate::core::pairwise_distances;
 
     use super::*;
 
@@ -78,8 +28,42 @@ mod tests {
     #[test]
     fn test_solve() {
         let image = parse(INPUT).unwrap();
-        assert_eq!(solve(&image, 1), 374);
-        assert_eq!(solve(&image, 9), 1030);
-        assert_eq!(solve(&image, 99), 8410);
+        assert_eq!(total_distance(&image, 2), 374);
+        assert_eq!(total_distance(&image, 10), 1030);
+        assert_eq!(total_distance(&image, 100), 8410);
+    }
+
+    #[test]
+    fn test_pairwise_distances() {
+        let image = parse(INPUT).unwrap();
+
+        let distances: Vec<_> = pairwise_distances(&image, 2).collect();
+        assert_eq!(distances.len(), 36);
+        assert!(distances.contains(&(((5, 1), (9, 4)), 9)));
+        assert!(distances.contains(&(((0, 3), (8, 7)), 15)));
+        assert!(distances.contains(&(((2, 0), (6, 9)), 17)));
+        assert!(distances.contains(&(((9, 0), (9, 4)), 5)));
+
+        for factor in [0, 1, 2, 10, 1_000_000, 10u128.pow(30)] {
+            assert_eq!(
+                pairwise_distances(&image, factor)
+                    .map(|(_, distance)| distance)
+                    .sum::<Distance>(),
+                total_distance(&image, factor),
+                "factor {}",
+                factor
+            );
+        }
+
+        // With no expansion, every pair is just as far apart as in the image.
+        assert!(pairwise_distances(&image, 1).all(
+            |(((row1, column1), (row2, column2)), distance)| {
+                distance == (row1.abs_diff(row2) + column1.abs_diff(column2)) as Distance
+            }
+        ));
+
+        // Removing empty rows and columns entirely pulls the galaxies together.
+        assert_eq!(total_distance(&image, 1), 292);
+        assert_eq!(total_distance(&image, 0), 210);
     }
 }
@@ -0,0 +1,95 @@
+use itertools::Itertools;
+
+use super::{Image, Location};
+
+pub type Distance = u128;
+
+// Every empty row and column becomes `factor` of them, so a factor of 1 leaves the image as it
+// is and a factor of 0 removes them entirely.
+fn expanded_coordinates(occupied: impl Iterator<Item = usize>, factor: Distance) -> Vec<Distance> {
+    let occupied: Vec<_> = occupied.collect();
+    let len = occupied.iter().max().map_or(0, |&max| max + 1);
+
+    let mut is_occupied = vec![false; len];
+    for index in occupied {
+        is_occupied[index] = true;
+    }
+
+    let mut coordinates = Vec::with_capacity(len);
+    let mut coordinate = 0;
+
+    for occupied in is_occupied {
+        coordinates.push(coordinate);
+        coordinate += if occupied { 1 } else { factor };
+    }
+
+    coordinates
+}
+
+#[derive(Debug, Clone)]
+pub struct Expansion {
+    rows: Vec<Distance>,
+    columns: Vec<Distance>,
+}
+
+impl Expansion {
+    pub fn new(image: &Image, factor: Distance) -> Self {
+        Self {
+            rows: expanded_coordinates(image.iter().map(|&(row, _)| row), factor),
+            columns: expanded_coordinates(image.iter().map(|&(_, column)| column), factor),
+        }
+    }
+
+    // Only the rows and columns up to the last galaxy are expanded.
+    pub fn expand(&self, (row, column): Location) -> (Distance, Distance) {
+        (self.rows[row], self.columns[column])
+    }
+}
+
+fn galaxies(image: &Image) -> Vec<Location> {
+    image.iter().copied().sorted().collect()
+}
+
+pub fn pairwise_distances(
+    image: &Image,
+    factor: Distance,
+) -> impl Iterator<Item = ((Location, Location), Distance)> {
+    let expansion = Expansion::new(image, factor);
+
+    galaxies(image)
+        .into_iter()
+        .tuple_combinations()
+        .map(move |(a, b)| {
+            let (row1, column1) = expansion.expand(a);
+            let (row2, column2) = expansion.expand(b);
+
+            ((a, b), row1.abs_diff(row2) + column1.abs_diff(column2))
+        })
+}
+
+// Once sorted, each coordinate is at least as far along as all of the ones before it, so its
+// distance to all of them is its own value times how many there are, minus their sum.
+fn sum_of_differences(mut coordinates: Vec<Distance>) -> Distance {
+    coordinates.sort_unstable();
+
+    let mut total = 0;
+    let mut sum_before = 0;
+
+    for (i, coordinate) in coordinates.into_iter().enumerate() {
+        total += coordinate * i as Distance - sum_before;
+        sum_before += coordinate;
+    }
+
+    total
+}
+
+pub fn total_distance(image: &Image, factor: Distance) -> Distance {
+    let expansion = Expansion::new(image, factor);
+
+    let (rows, columns) = image
+        .iter()
+        .map(|&location| expansion.expand(location))
+        .unzip();
+
+    sum_of_differences(rows) + sum_of_differences(columns)
+}
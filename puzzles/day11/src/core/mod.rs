@@ -1,2 +1,5 @@
 pub type Location = (usize, usize);
 pub type Image = std::collections::HashSet<Location>;
+
+pub mod expansion;
+pub use expansion::*;
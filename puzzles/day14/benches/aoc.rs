@@ -3,6 +3,7 @@ use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion
 use aoc::{Input, Parse, Solve};
 
 use day14::aoc::{Parser1, Parser2, Solver1, Solver2};
+use day14::core::{Bitboard, Platform, SPIN_CYCLE};
 
 const INPUT: Input = include_str!("../input");
 
@@ -30,6 +31,38 @@ fn part2(c: &mut Criterion) {
     benchmark::<Parser2, Solver2>(c, "part2");
 }
 
-criterion_group!(benches, part1, part2);
+fn spin_cycle(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("{}/{}", stringify!(day14), "spin_cycle"));
+
+    let platform = Platform::try_from(INPUT).unwrap();
+    let board = Bitboard::try_from(&platform).unwrap();
+
+    group.bench_function("platform", |b| {
+        b.iter_batched(
+            || platform.clone(),
+            |platform| {
+                SPIN_CYCLE
+                    .iter()
+                    .fold(platform, |platform, direction| platform.tilt(direction))
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.bench_function("bitboard", |b| {
+        b.iter_batched(
+            || board.clone(),
+            |mut board| {
+                board.spin_cycle();
+                board
+            },
+            BatchSize::SmallInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, part1, part2, spin_cycle);
 
 criterion_main!(benches);
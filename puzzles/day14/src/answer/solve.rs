@@ -1,10 +1,6 @@
-use std::collections::{
-    hash_map::{DefaultHasher, Entry},
-    HashMap,
-};
-use std::hash::{Hash, Hasher};
+use std::collections::{hash_map::Entry, HashMap};
 
-use crate::core::Direction;
+use crate::core::{Bitboard, Direction};
 
 use super::{Parsed1, Parsed2};
 
@@ -16,50 +12,32 @@ pub fn solve1(platform: &Parsed1) -> anyhow::Result<Solution1> {
     Ok(platform.tilt(&Direction::North).total_load())
 }
 
-fn calculate_hash<T: Hash>(t: &T) -> u64 {
-    let mut s = DefaultHasher::new();
-    t.hash(&mut s);
-    s.finish()
-}
-
+// The cube rocks never move, so the round rocks are enough to tell the boards apart, and only the
+// load of each one needs to be kept.
 pub fn solve2(platform: &Parsed2) -> anyhow::Result<Solution2> {
-    let directions_ccw = [
-        Direction::North,
-        Direction::West,
-        Direction::South,
-        Direction::East,
-    ];
-
     let num_cycles = 1_000_000_000;
-    let num_iterations = num_cycles * directions_ccw.len();
-    let mut directions = directions_ccw.into_iter().cycle().peekable();
-
-    let mut current = calculate_hash(platform);
-    let mut platforms = HashMap::from([(current, platform.clone())]);
-    let mut graph: HashMap<(u64, Direction), (usize, u64)> = HashMap::new();
-
-    let (loop_start, loop_end) = (0..num_iterations)
-        .find_map(
-            |i| match graph.entry((current, *directions.peek().unwrap())) {
-                Entry::Occupied(entry) => Some((entry.get().0, i)),
-                Entry::Vacant(entry) => {
-                    let platform = platforms[&current].tilt(&directions.next().unwrap());
-                    current = calculate_hash(&platform);
-                    platforms.insert(current, platform);
-                    entry.insert((i, current));
-                    None
-                }
-            },
-        )
-        .unwrap();
-
-    let remaining_iterations = (num_iterations - loop_start) % (loop_end - loop_start);
 
-    for _ in 0..remaining_iterations {
-        current = graph.get(&(current, directions.next().unwrap())).unwrap().1;
+    let mut board = Bitboard::try_from(platform)?;
+    let mut loads = vec![board.total_load()];
+    let mut seen = HashMap::from([(board.round_rocks().to_vec(), 0)]);
+
+    for i in 1..=num_cycles {
+        board.spin_cycle();
+
+        match seen.entry(board.round_rocks().to_vec()) {
+            Entry::Occupied(entry) => {
+                let loop_start = *entry.get();
+                let index = loop_start + (num_cycles - loop_start) % (i - loop_start);
+                return Ok(loads[index]);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(i);
+                loads.push(board.total_load());
+            }
+        }
     }
 
-    Ok(platforms[&current].total_load())
+    Ok(board.total_load())
 }
 
 #[cfg(test)]
@@ -0,0 +1,276 @@
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+
+use anyhow::ensure;
+
+use super::{Direction, Platform, Tile, SPIN_CYCLE};
+
+pub type Row = u128;
+
+pub const MAX_WIDTH: usize = Row::BITS as usize;
+
+// The bits for columns `start..end`.
+fn mask(columns: &Range<usize>) -> Row {
+    match columns.len() {
+        0 => 0,
+        MAX_WIDTH => Row::MAX,
+        len => ((1 << len) - 1) << columns.start,
+    }
+}
+
+// The runs of positions between the cube rocks, which are the only places round rocks can move.
+fn segments(len: usize, is_cube: impl Fn(usize) -> bool) -> Vec<Range<usize>> {
+    let mut segments = Vec::new();
+    let mut start = 0;
+
+    for i in 0..=len {
+        if i == len || is_cube(i) {
+            if start < i {
+                segments.push(start..i);
+            }
+            start = i + 1;
+        }
+    }
+
+    segments
+}
+
+// A platform packed into one bitset of round rocks and one of cube rocks for each row, where bit
+// `j` is column `j`.
+//
+// Tilting east or west works on a whole row segment at once: the cube rocks never move, so the
+// segments between them are found once and tilting only has to count the round rocks in each
+// segment and pack them against one end. Tilting north or south rolls whole rows into each other
+// instead, so every column moves at once.
+#[derive(Debug, Clone)]
+pub struct Bitboard {
+    height: usize,
+    width: usize,
+    round: Vec<Row>,
+    cube: Vec<Row>,
+    row_segments: Vec<Vec<Range<usize>>>,
+}
+
+impl Bitboard {
+    fn new(height: usize, width: usize, round: Vec<Row>, cube: Vec<Row>) -> Self {
+        let row_segments = cube
+            .iter()
+            .map(|&bits| segments(width, |column| bits & (1 << column) != 0))
+            .collect();
+
+        Self {
+            height,
+            width,
+            round,
+            cube,
+            row_segments,
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    // The only part of the board that changes when it's tilted.
+    pub fn round_rocks(&self) -> &[Row] {
+        &self.round
+    }
+
+    pub fn tilt(&mut self, direction: &Direction) {
+        match direction {
+            Direction::East | Direction::West => {
+                for (bits, segments) in self.round.iter_mut().zip(&self.row_segments) {
+                    for segment in segments {
+                        let segment_mask = mask(segment);
+                        let count = (*bits & segment_mask).count_ones() as usize;
+
+                        let packed = if direction == &Direction::West {
+                            segment.start..segment.start + count
+                        } else {
+                            segment.end - count..segment.end
+                        };
+
+                        *bits = *bits & !segment_mask | mask(&packed);
+                    }
+                }
+            }
+            Direction::North | Direction::South => {
+                let rows: Vec<usize> = if direction == &Direction::North {
+                    (0..self.height).collect()
+                } else {
+                    (0..self.height).rev().collect()
+                };
+
+                // The rows already passed have settled, so each row's rocks keep rolling into the
+                // next one over until they run into something there, all columns at once.
+                for (i, &row) in rows.iter().enumerate() {
+                    let mut rolling = std::mem::take(&mut self.round[row]);
+                    let mut at = i;
+
+                    while rolling != 0 {
+                        if at == 0 {
+                            self.round[rows[0]] |= rolling;
+                            break;
+                        }
+
+                        let next = rows[at - 1];
+                        let stopped = rolling & (self.round[next] | self.cube[next]);
+
+                        self.round[rows[at]] |= stopped;
+                        rolling &= !stopped;
+                        at -= 1;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn spin_cycle(&mut self) {
+        for direction in &SPIN_CYCLE {
+            self.tilt(direction);
+        }
+    }
+
+    pub fn total_load(&self) -> usize {
+        self.round
+            .iter()
+            .enumerate()
+            .map(|(row, bits)| bits.count_ones() as usize * (self.height - row))
+            .sum()
+    }
+}
+
+// Boards are compared by their rocks alone, since the segments only depend on the cube rocks.
+impl PartialEq for Bitboard {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width && self.round == other.round && self.cube == other.cube
+    }
+}
+
+impl Eq for Bitboard {}
+
+// Only the round rocks are hashed, because they are the only thing that changes between boards
+// made from the same platform.
+impl Hash for Bitboard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.round.hash(state);
+    }
+}
+
+impl std::convert::TryFrom<&Platform> for Bitboard {
+    type Error = anyhow::Error;
+
+    fn try_from(platform: &Platform) -> Result<Self, Self::Error> {
+        let (height, width) = platform.shape();
+
+        ensure!(
+            width <= MAX_WIDTH,
+            "expected at most {} columns, but there were {}",
+            MAX_WIDTH,
+            width
+        );
+
+        let mut round = vec![0; height];
+        let mut cube = vec![0; height];
+
+        for row in 0..height {
+            for column in 0..width {
+                match platform.get((row, column)) {
+                    Some(Tile::RoundRock) => round[row] |= 1 << column,
+                    Some(Tile::CubeRock) => cube[row] |= 1 << column,
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(Self::new(height, width, round, cube))
+    }
+}
+
+impl From<&Bitboard> for Platform {
+    fn from(board: &Bitboard) -> Self {
+        let tiles = (0..board.height).flat_map(|row| {
+            (0..board.width).map(move |column| {
+                let bit = 1 << column;
+
+                if board.round[row] & bit != 0 {
+                    Tile::RoundRock
+                } else if board.cube[row] & bit != 0 {
+                    Tile::CubeRock
+                } else {
+                    Tile::Empty
+                }
+            })
+        });
+
+        Platform::from_tiles(board.height, board.width, tiles)
+    }
+}
+
+impl std::fmt::Display for Bitboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Platform::from(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::Input;
+
+    use super::*;
+
+    const INPUT: Input = include_str!("../../input-test");
+
+    #[test]
+    fn test_conversion() -> anyhow::Result<()> {
+        let platform = Platform::try_from(INPUT)?;
+        let board = Bitboard::try_from(&platform)?;
+
+        assert_eq!(board.shape(), platform.shape());
+        assert_eq!(Platform::from(&board), platform);
+        assert_eq!(board.to_string(), INPUT);
+
+        let wide = Platform::try_from(format!("{}\n", ".".repeat(MAX_WIDTH + 1)).as_str())?;
+        assert!(Bitboard::try_from(&wide).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_tilt() -> anyhow::Result<()> {
+        let platform = Platform::try_from(INPUT)?;
+
+        for direction in [
+            Direction::North,
+            Direction::East,
+            Direction::South,
+            Direction::West,
+        ] {
+            let mut board = Bitboard::try_from(&platform)?;
+            board.tilt(&direction);
+
+            let expected = platform.tilt(&direction);
+            assert_eq!(Platform::from(&board), expected, "{:?}", direction);
+            assert_eq!(board.total_load(), expected.total_load());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spin_cycle() -> anyhow::Result<()> {
+        let mut platform = Platform::try_from(INPUT)?;
+        let mut board = Bitboard::try_from(&platform)?;
+
+        for _ in 0..3 {
+            board.spin_cycle();
+            platform = SPIN_CYCLE
+                .iter()
+                .fold(platform, |platform, direction| platform.tilt(direction));
+
+            assert_eq!(board, Bitboard::try_from(&platform)?);
+        }
+
+        Ok(())
+    }
+}
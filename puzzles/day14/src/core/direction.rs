@@ -16,3 +16,11 @@ impl Direction {
         }
     }
 }
+
+// One spin cycle tilts the platform in each of these directions, in order.
+pub const SPIN_CYCLE: [Direction; 4] = [
+    Direction::North,
+    Direction::West,
+    Direction::South,
+    Direction::East,
+];
@@ -8,3 +8,6 @@ pub use tile::*;
 
 pub mod platform;
 pub use platform::*;
+
+pub mod bitboard;
+pub use bitboard::*;
@@ -14,6 +14,18 @@ fn step((row, column): Location, (vertical, horizontal): (isize, isize)) -> Opti
 }
 
 impl Platform {
+    pub fn from_tiles(height: usize, width: usize, tiles: impl IntoIterator<Item = Tile>) -> Self {
+        Self(Inner::from_row_iterator(height, width, tiles))
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        self.0.shape()
+    }
+
+    pub fn get(&self, location: Location) -> Option<&Tile> {
+        self.0.get(location)
+    }
+
     pub fn tilt(&self, direction: &Direction) -> Self {
         let offset = direction.unit();
         let (height, width) = self.0.shape();
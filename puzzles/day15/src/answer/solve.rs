@@ -1,68 +1,23 @@
-use crate::core::Operation;
+use crate::core::{calculate_hash, LensMap};
 
 use super::{Parsed1, Parsed2};
 
-type Hash = usize;
-
 type Solution = usize;
 pub type Solution1 = Solution;
 pub type Solution2 = Solution;
 
-fn calculate_hash(s: &str) -> Hash {
-    let mut current = 0;
-
-    for c in s.chars() {
-        current += c as Hash;
-        current *= 17;
-        current %= 256;
-    }
-
-    current
-}
-
 pub fn solve1(steps: &Parsed1) -> anyhow::Result<Solution1> {
     Ok(steps.iter().map(|&step| calculate_hash(step)).sum())
 }
 
 pub fn solve2(steps: &Parsed2) -> anyhow::Result<Solution2> {
-    type LensBox<'a> = Vec<(&'a str, usize)>;
-
-    let mut lens_boxes: Vec<LensBox> = vec![vec![]; 256];
-
-    let find_lens = |lens_box: &LensBox, query: &str| -> Option<usize> {
-        lens_box.iter().position(|&(label, _)| label == query)
-    };
+    let mut lens_map = LensMap::new();
 
     for step in steps {
-        let i = calculate_hash(step.label);
-        let lens_box = lens_boxes.get_mut(i).unwrap();
-
-        match step.operation {
-            Operation::Remove => {
-                if let Some(j) = find_lens(lens_box, step.label) {
-                    lens_box.remove(j);
-                }
-            }
-            Operation::Set(value) => {
-                if let Some(j) = find_lens(lens_box, step.label) {
-                    lens_box[j] = (step.label, value);
-                } else {
-                    lens_box.push((step.label, value));
-                }
-            }
-        };
+        lens_map.apply(step);
     }
 
-    Ok(lens_boxes
-        .into_iter()
-        .enumerate()
-        .flat_map(|(i, lens_box)| {
-            lens_box
-                .into_iter()
-                .enumerate()
-                .map(move |(j, (_, value))| (i + 1) * (j + 1) * value)
-        })
-        .sum())
+    Ok(lens_map.focusing_power())
 }
 
 #[cfg(test)]
@@ -0,0 +1,35 @@
+use std::hash::{BuildHasherDefault, Hasher};
+
+pub type Hash = usize;
+
+pub const BUCKET_COUNT: usize = 256;
+
+// The Holiday ASCII String Helper algorithm.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HolidayHasher {
+    current: Hash,
+}
+
+impl Hasher for HolidayHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.current += byte as Hash;
+            self.current *= 17;
+            self.current %= BUCKET_COUNT;
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.current as u64
+    }
+}
+
+// Hashing a `str` through `Hash` also writes a byte to mark where it ends, so the hashes will not
+// match `calculate_hash`, but they will still spread keys across the same 256 values.
+pub type HolidayBuildHasher = BuildHasherDefault<HolidayHasher>;
+
+pub fn calculate_hash(s: &str) -> Hash {
+    let mut hasher = HolidayHasher::default();
+    hasher.write(s.as_bytes());
+    hasher.finish() as Hash
+}
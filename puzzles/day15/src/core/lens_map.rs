@@ -0,0 +1,162 @@
+use super::{calculate_hash, Operation, Step, BUCKET_COUNT};
+
+pub type FocalLength = usize;
+pub type Lens<'a> = (&'a str, FocalLength);
+
+// Labels are sorted into boxes by their hash, and each box keeps its lenses in the order they
+// were first inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LensMap<'a> {
+    boxes: Vec<Vec<Lens<'a>>>,
+}
+
+impl Default for LensMap<'_> {
+    fn default() -> Self {
+        Self {
+            boxes: vec![Vec::new(); BUCKET_COUNT],
+        }
+    }
+}
+
+impl<'a> LensMap<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn position(&self, label: &str) -> (usize, Option<usize>) {
+        let i = calculate_hash(label);
+        let j = self.boxes[i].iter().position(|&(other, _)| other == label);
+        (i, j)
+    }
+
+    pub fn get(&self, label: &str) -> Option<FocalLength> {
+        let (i, j) = self.position(label);
+        j.map(|j| self.boxes[i][j].1)
+    }
+
+    // Replacing a lens keeps its place in the box. Returns the focal length of the lens it
+    // replaced, if any.
+    pub fn insert(&mut self, label: &'a str, focal_length: FocalLength) -> Option<FocalLength> {
+        match self.position(label) {
+            (i, Some(j)) => Some(std::mem::replace(&mut self.boxes[i][j].1, focal_length)),
+            (i, None) => {
+                self.boxes[i].push((label, focal_length));
+                None
+            }
+        }
+    }
+
+    // The lenses behind the removed one move forward to fill its place.
+    pub fn remove(&mut self, label: &str) -> Option<FocalLength> {
+        let (i, j) = self.position(label);
+        j.map(|j| self.boxes[i].remove(j).1)
+    }
+
+    pub fn apply(&mut self, step: &Step<'a>) {
+        match step.operation {
+            Operation::Remove => {
+                self.remove(step.label);
+            }
+            Operation::Set(focal_length) => {
+                self.insert(step.label, focal_length);
+            }
+        }
+    }
+
+    pub fn lens_box(&self, i: usize) -> &[Lens<'a>] {
+        &self.boxes[i]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Lens<'a>> + '_ {
+        self.boxes.iter().flatten().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.boxes.iter().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes.iter().all(Vec::is_empty)
+    }
+
+    pub fn focusing_power(&self) -> usize {
+        self.boxes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, lens_box)| {
+                lens_box
+                    .iter()
+                    .enumerate()
+                    .map(move |(j, &(_, focal_length))| (i + 1) * (j + 1) * focal_length)
+            })
+            .sum()
+    }
+}
+
+// Only the boxes with lenses in them are shown.
+impl std::fmt::Display for LensMap<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for (i, lens_box) in self.boxes.iter().enumerate() {
+            if lens_box.is_empty() {
+                continue;
+            }
+
+            write!(f, "Box {}:", i)?;
+
+            for (label, focal_length) in lens_box {
+                write!(f, " [{} {}]", label, focal_length)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::core::HolidayBuildHasher;
+
+    use super::*;
+
+    #[test]
+    fn test_lens_map() {
+        let mut map = LensMap::new();
+        assert!(map.is_empty());
+
+        assert_eq!(map.insert("rn", 1), None);
+        assert_eq!(map.insert("cm", 2), None);
+        assert_eq!(map.insert("qp", 3), None);
+        assert_eq!(map.lens_box(0), [("rn", 1), ("cm", 2)]);
+        assert_eq!(map.lens_box(1), [("qp", 3)]);
+
+        assert_eq!(map.insert("rn", 7), Some(1));
+        assert_eq!(map.lens_box(0), [("rn", 7), ("cm", 2)]);
+        assert_eq!(map.get("rn"), Some(7));
+        assert_eq!(map.get("ot"), None);
+
+        assert_eq!(map.remove("rn"), Some(7));
+        assert_eq!(map.remove("rn"), None);
+        assert_eq!(map.lens_box(0), [("cm", 2)]);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.iter().collect::<Vec<_>>(), [("cm", 2), ("qp", 3)]);
+        assert_eq!(map.focusing_power(), 2 + 2 * 3);
+    }
+
+    #[test]
+    fn test_hasher() {
+        let mut map: HashMap<&str, FocalLength, HolidayBuildHasher> = HashMap::default();
+
+        map.insert("rn", 1);
+        map.insert("cm", 2);
+        map.insert("rn", 3);
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get("rn"), Some(&3));
+        assert_eq!(map.get("cm"), Some(&2));
+    }
+}
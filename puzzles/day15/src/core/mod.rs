@@ -3,3 +3,12 @@ pub use operation::*;
 
 pub mod step;
 pub use step::*;
+
+pub mod hash;
+pub use hash::*;
+
+pub mod lens_map;
+pub use lens_map::*;
+
+pub mod replay;
+pub use replay::*;
@@ -27,3 +27,12 @@ impl std::convert::TryFrom<&str> for Operation {
         }
     }
 }
+
+impl std::fmt::Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Remove => write!(f, "-"),
+            Self::Set(value) => write!(f, "={}", value),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use super::{LensMap, Step};
+
+// The state of every box after each step, starting from all of them being empty.
+pub fn replay<'s, 'a>(
+    steps: &'s [Step<'a>],
+) -> impl Iterator<Item = (&'s Step<'a>, LensMap<'a>)> + 's {
+    steps.iter().scan(LensMap::new(), |lens_map, step| {
+        lens_map.apply(step);
+        Some((step, lens_map.clone()))
+    })
+}
+
+pub fn render_trace(steps: &[Step]) -> String {
+    replay(steps)
+        .map(|(step, lens_map)| format!("After \"{}\":\n{}", step, lens_map))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../../input-test");
+
+    const TRACE: &str = r#"After "rn=1":
+Box 0: [rn 1]
+
+After "cm-":
+Box 0: [rn 1]
+
+After "qp=3":
+Box 0: [rn 1]
+Box 1: [qp 3]
+
+After "cm=2":
+Box 0: [rn 1] [cm 2]
+Box 1: [qp 3]
+
+After "qp-":
+Box 0: [rn 1] [cm 2]
+
+After "pc=4":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4]
+
+After "ot=9":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9]
+
+After "ab=5":
+Box 0: [rn 1] [cm 2]
+Box 3: [pc 4] [ot 9] [ab 5]
+
+After "pc-":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5]
+
+After "pc=6":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 9] [ab 5] [pc 6]
+
+After "ot=7":
+Box 0: [rn 1] [cm 2]
+Box 3: [ot 7] [ab 5] [pc 6]
+"#;
+
+    fn parse(input: &str) -> anyhow::Result<Vec<Step<'_>>> {
+        input.trim().split(',').map(Step::try_from).collect()
+    }
+
+    #[test]
+    fn test_replay() -> anyhow::Result<()> {
+        let steps = parse(INPUT)?;
+        let states: Vec<_> = replay(&steps).collect();
+
+        assert_eq!(states.len(), steps.len());
+        assert_eq!(states[4].0.to_string(), "qp-");
+        assert_eq!(states[4].1.to_string(), "Box 0: [rn 1] [cm 2]\n");
+        assert_eq!(states.last().unwrap().1.focusing_power(), 145);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_trace() -> anyhow::Result<()> {
+        assert_eq!(render_trace(&parse(INPUT)?), TRACE);
+        Ok(())
+    }
+}
@@ -13,7 +13,7 @@ impl<'a> std::convert::TryFrom<&'a str> for Step<'a> {
 
     fn try_from(input: &'a str) -> Result<Self, Self::Error> {
         let i = input
-            .find(|c: char| c == '=' || c == '-')
+            .find(['=', '-'])
             .context("expected value to start with a '=' or '-'")?;
 
         let (label, rest) = input.split_at(i);
@@ -23,3 +23,9 @@ impl<'a> std::convert::TryFrom<&'a str> for Step<'a> {
         Ok(Self { label, operation })
     }
 }
+
+impl std::fmt::Display for Step<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}{}", self.label, self.operation)
+    }
+}
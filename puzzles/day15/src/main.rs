@@ -1,10 +1,17 @@
 use aoc::{Input, Parse, Solve};
 
 use day15::aoc::{Parser1, Parser2, Solver1, Solver2};
+use day15::core::render_trace;
 
 const INPUT: Input = include_str!("../input");
 
 fn main() -> anyhow::Result<()> {
+    // Show the boxes after every step of the initialization sequence instead of solving.
+    if std::env::args().skip(1).any(|arg| arg == "--trace") {
+        print!("{}", render_trace(&Parser2(INPUT).parse()?));
+        return Ok(());
+    }
+
     println!(
         "Part 1 solution: {:?}",
         Solver1(Parser1(INPUT).parse()?).solve()?
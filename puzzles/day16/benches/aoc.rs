@@ -2,6 +2,7 @@ use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion
 
 use aoc::{Input, Parse, Solve};
 
+use day16::answer::parse2;
 use day16::aoc::{Parser1, Parser2, Solver1, Solver2};
 
 const INPUT: Input = include_str!("../input");
@@ -30,6 +31,23 @@ fn part2(c: &mut Criterion) {
     benchmark::<Parser2, Solver2>(c, "part2");
 }
 
-criterion_group!(benches, part1, part2);
+fn edge_starts(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("{}/{}", stringify!(day16), "edge-starts"));
+
+    let contraption = parse2(INPUT).unwrap();
+
+    group.bench_function("serial", |b| {
+        b.iter(|| black_box(&contraption).best_start())
+    });
+
+    #[cfg(feature = "parallel")]
+    group.bench_function("parallel", |b| {
+        b.iter(|| black_box(&contraption).par_best_start())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, part1, part2, edge_starts);
 
 criterion_main!(benches);
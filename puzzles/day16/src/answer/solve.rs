@@ -15,16 +15,15 @@ pub fn solve1(contraption: &Parsed1) -> anyhow::Result<Solution1> {
 }
 
 pub fn solve2(contraption: &Parsed2) -> anyhow::Result<Solution2> {
-    contraption
-        .edge_locations()
-        .into_iter()
-        .flat_map(|(enter_from, locations)| {
-            locations
-                .into_iter()
-                .map(move |start| contraption.count_energized(start, enter_from))
-        })
-        .max()
-        .context("no solution")
+    #[cfg(not(feature = "parallel"))]
+    let best = contraption.best_start();
+
+    #[cfg(feature = "parallel")]
+    let best = contraption.par_best_start();
+
+    let (_, _, count) = best.context("no solution")?;
+
+    Ok(count)
 }
 
 #[cfg(test)]
@@ -48,4 +47,18 @@ mod tests {
         assert_eq!(solve2(&parse2(INPUT)?)?, 51);
         Ok(())
     }
+
+    #[test]
+    fn test_best_start() -> anyhow::Result<()> {
+        assert_eq!(parse2(INPUT)?.best_start(), Some(((0, 3), North, 51)));
+        Ok(())
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_par_best_start() -> anyhow::Result<()> {
+        let contraption = parse2(INPUT)?;
+        assert_eq!(contraption.par_best_start(), contraption.best_start());
+        Ok(())
+    }
 }
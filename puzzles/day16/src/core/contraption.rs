@@ -3,6 +3,9 @@ use anyhow::{ensure, Context};
 use std::collections::{HashSet, VecDeque};
 use std::iter::repeat;
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
 use super::{Direction, Location, Offset, Tile};
 
 use Direction::*;
@@ -37,6 +40,30 @@ impl Contraption {
         ]
     }
 
+    pub fn edge_starts(&self) -> Vec<(Location, Direction)> {
+        self.edge_locations()
+            .into_iter()
+            .flat_map(|(enter_from, locations)| {
+                locations.into_iter().map(move |start| (start, enter_from))
+            })
+            .collect()
+    }
+
+    pub fn best_start(&self) -> Option<(Location, Direction, usize)> {
+        self.edge_starts()
+            .into_iter()
+            .map(|(start, enter_from)| (start, enter_from, self.count_energized(start, enter_from)))
+            .max_by_key(|&(_, _, count)| count)
+    }
+
+    #[cfg(feature = "parallel")]
+    pub fn par_best_start(&self) -> Option<(Location, Direction, usize)> {
+        self.edge_starts()
+            .into_par_iter()
+            .map(|(start, enter_from)| (start, enter_from, self.count_energized(start, enter_from)))
+            .max_by_key(|&(_, _, count)| count)
+    }
+
     pub fn count_energized(&self, start: Location, enter_from: Direction) -> usize {
         let mut frontier = VecDeque::from([(start, enter_from)]);
         let mut energized: HashSet<(Location, Direction)> = Default::default();
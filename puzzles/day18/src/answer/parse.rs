@@ -1,4 +1,4 @@
-use anyhow::{ensure, Context};
+use anyhow::Context;
 
 use aoc::Input;
 
@@ -15,19 +15,27 @@ fn parse_code(input: &str) -> anyhow::Result<(&str, &str, &str)> {
     let token2 = tokens.next().context("missing token 2")?;
     let token3 = tokens.next().context("missing token 3")?;
 
+    let token3 = token3
+        .strip_prefix('(')
+        .context("missing opening parentheses")?
+        .strip_suffix(')')
+        .context("missing closing parentheses")?;
+
     Ok((token1, token2, token3))
 }
 
 pub fn parse1(input: Input) -> anyhow::Result<Parsed1> {
     fn parse_instruction(input: &str) -> anyhow::Result<Instruction> {
-        let (first, second, _) = parse_code(input)?;
+        let (first, second, third) = parse_code(input)?;
 
         let direction = first.try_into().context("direction")?;
         let distance = second.parse().context("distance")?;
+        let color = third.try_into().context("color")?;
 
         Ok(Instruction {
             direction,
             distance,
+            color,
         })
     }
 
@@ -42,23 +50,12 @@ pub fn parse1(input: Input) -> anyhow::Result<Parsed1> {
 
 pub fn parse2(input: Input) -> anyhow::Result<Parsed2> {
     fn parse_instruction(input: &str) -> anyhow::Result<Instruction> {
-        let (_, _, encoded) = parse_code(input)?;
-
-        let encoded = encoded
-            .strip_prefix('(')
-            .context("missing opening parentheses")?
-            .strip_suffix(')')
-            .context("missing closing parentheses")?
-            .strip_prefix('#')
-            .context("missing hash")?;
+        let (_, _, third) = parse_code(input)?;
 
-        ensure!(
-            encoded.len() == 6,
-            "expected hexadecimal code to be 6 digits, but it was {}",
-            encoded.len()
-        );
+        let color = third.try_into().context("color")?;
 
-        let (first, second) = encoded.split_at(5);
+        // The color was parsed successfully, so this will be six hexadecimal digits.
+        let (first, second) = third[1..].split_at(5);
 
         let distance = usize::from_str_radix(first, 16)?;
         let direction = second.parse::<u8>()?.try_into()?;
@@ -66,6 +63,7 @@ pub fn parse2(input: Input) -> anyhow::Result<Parsed2> {
         Ok(Instruction {
             direction,
             distance,
+            color,
         })
     }
 
@@ -97,4 +95,15 @@ mod tests {
         dbg!(parse2(INPUT)?);
         Ok(())
     }
+
+    #[test]
+    fn test_color() -> anyhow::Result<()> {
+        let color = "#70c710".try_into()?;
+
+        assert_eq!(parse1(INPUT)?[0].color, color);
+        assert_eq!(parse2(INPUT)?[0].color, color);
+        assert_eq!(format!("{}", color), "#70c710");
+
+        Ok(())
+    }
 }
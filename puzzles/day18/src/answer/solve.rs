@@ -1,4 +1,4 @@
-use crate::core::{Instruction, Point};
+use crate::core::{Instruction, Polygon};
 
 use super::{Parsed1, Parsed2};
 
@@ -6,43 +6,17 @@ type Solution = usize;
 pub type Solution1 = Solution;
 pub type Solution2 = Solution;
 
-fn solve(instructions: &[Instruction]) -> usize {
-    let vertices: Vec<_> = instructions
-        .iter()
-        .scan(Point::from([0, 0]), |prev_point, instruction| {
-            let vector = instruction.direction.unit() * instruction.distance as isize;
-            let next_point = *prev_point + vector;
-            *prev_point = next_point;
-            Some(next_point)
-        })
-        .collect();
-
-    // https://en.wikipedia.org/wiki/Shoelace_formula
-    let area = vertices
-        .iter()
-        .zip(vertices.iter().cycle().skip(1).take(vertices.len() + 1))
-        .map(|(&a, &b)| (a.x * b.y) - (a.y * b.x))
-        .sum::<isize>()
-        .unsigned_abs()
-        / 2;
-
-    let perimeter: usize = instructions
-        .iter()
-        .map(|instruction| instruction.distance)
-        .sum();
-
-    // https://en.m.wikipedia.org/wiki/Pick%27s_theorem
-    let inner = area - (perimeter / 2) + 1;
-
-    inner + perimeter
+fn solve(instructions: &[Instruction]) -> anyhow::Result<usize> {
+    let polygon: Polygon = instructions.try_into()?;
+    Ok(polygon.capacity())
 }
 
 pub fn solve1(instructions: &Parsed1) -> anyhow::Result<Solution1> {
-    Ok(solve(instructions))
+    solve(instructions)
 }
 
 pub fn solve2(instructions: &Parsed2) -> anyhow::Result<Solution2> {
-    Ok(solve(instructions))
+    solve(instructions)
 }
 
 #[cfg(test)]
@@ -50,6 +24,7 @@ mod tests {
     use aoc::Input;
 
     use crate::answer::{parse1, parse2};
+    use crate::core::{Color, Trench, Winding};
 
     use super::*;
 
@@ -66,4 +41,58 @@ mod tests {
         assert_eq!(solve2(&parse2(INPUT)?)?, 952_408_144_115);
         Ok(())
     }
+
+    #[test]
+    fn test_polygon() -> anyhow::Result<()> {
+        let polygon: Polygon = parse1(INPUT)?.as_slice().try_into()?;
+
+        assert_eq!(polygon.vertices().len(), 14);
+        assert_eq!(polygon.perimeter(), 38);
+        assert_eq!(polygon.interior(), Some(24));
+        assert_eq!(polygon.winding(), Winding::Clockwise);
+        assert!(!polygon.is_self_intersecting());
+
+        assert_eq!(
+            polygon.render_ascii(1),
+            concat!(
+                "#######\n",
+                "#.....#\n",
+                "###...#\n",
+                "..#...#\n",
+                "..#...#\n",
+                "###.###\n",
+                "#...#..\n",
+                "##..###\n",
+                ".#....#\n",
+                ".######\n",
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_trench() -> anyhow::Result<()> {
+        let trench: Trench = parse1(INPUT)?.as_slice().try_into()?;
+
+        let ppm = trench.render_ppm();
+        let header = b"P6\n7 10\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 7 * 10 * 3);
+
+        // The top right corner is where the first edge meets the second.
+        let pixel = |row: usize, column: usize| {
+            let i = header.len() + (row * 7 + column) * 3;
+            [ppm[i], ppm[i + 1], ppm[i + 2]]
+        };
+        assert_eq!(pixel(0, 1), Color::try_from("#70c710")?.to_bytes());
+        assert_eq!(pixel(0, 6), Color::try_from("#0dc571")?.to_bytes());
+        assert_eq!(pixel(1, 1), Color::default().to_bytes());
+
+        let svg = trench.render_svg();
+        assert_eq!(svg.matches("<line ").count(), 14);
+        assert!(svg.contains("stroke=\"#70c710\""));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,46 @@
+use anyhow::{ensure, Context};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    pub fn to_bytes(&self) -> [u8; 3] {
+        [self.red, self.green, self.blue]
+    }
+}
+
+impl std::convert::TryFrom<&str> for Color {
+    type Error = anyhow::Error;
+
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let input = input.strip_prefix('#').context("missing hash")?;
+
+        ensure!(input.is_ascii(), "expected hexadecimal code to be ascii");
+
+        ensure!(
+            input.len() == 6,
+            "expected hexadecimal code to be 6 digits, but it was {}",
+            input.len()
+        );
+
+        let channel = |i: usize, name: &str| {
+            u8::from_str_radix(&input[i..i + 2], 16).with_context(|| format!("{} channel", name))
+        };
+
+        Ok(Self {
+            red: channel(0, "red")?,
+            green: channel(2, "green")?,
+            blue: channel(4, "blue")?,
+        })
+    }
+}
+
+impl std::fmt::Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+}
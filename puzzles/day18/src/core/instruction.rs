@@ -1,7 +1,8 @@
-use super::Direction;
+use super::{Color, Direction};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Instruction {
     pub direction: Direction,
     pub distance: usize,
+    pub color: Color,
 }
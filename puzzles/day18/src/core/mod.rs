@@ -3,8 +3,17 @@ use nalgebra::{Point2, Vector2};
 pub type Point = Point2<isize>;
 pub type Vector = Vector2<isize>;
 
+pub mod color;
+pub use color::*;
+
 pub mod direction;
 pub use direction::*;
 
 pub mod instruction;
 pub use instruction::*;
+
+pub mod polygon;
+pub use polygon::*;
+
+pub mod trench;
+pub use trench::*;
@@ -0,0 +1,325 @@
+use anyhow::ensure;
+
+use std::cmp::{max, min};
+
+use super::{Instruction, Point};
+
+use Winding::*;
+
+type Edge = (Point, Point);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+fn lower(&(a, b): &Edge) -> Point {
+    Point::new(min(a.x, b.x), min(a.y, b.y))
+}
+
+fn upper(&(a, b): &Edge) -> Point {
+    Point::new(max(a.x, b.x), max(a.y, b.y))
+}
+
+// Every edge is axis-aligned, so it is the same as its own bounding box and the overlap of two
+// edges is the overlap of their bounding boxes.
+fn overlap(a: &Edge, b: &Edge) -> Option<Edge> {
+    let (a_lower, a_upper) = (lower(a), upper(a));
+    let (b_lower, b_upper) = (lower(b), upper(b));
+
+    let start = Point::new(max(a_lower.x, b_lower.x), max(a_lower.y, b_lower.y));
+    let end = Point::new(min(a_upper.x, b_upper.x), min(a_upper.y, b_upper.y));
+
+    (start.x <= end.x && start.y <= end.y).then_some((start, end))
+}
+
+impl Polygon {
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.vertices
+            .iter()
+            .copied()
+            .zip(self.vertices.iter().copied().cycle().skip(1))
+    }
+
+    pub fn bounds(&self) -> (Point, Point) {
+        self.edges().fold(
+            (self.vertices[0], self.vertices[0]),
+            |(lower_bound, upper_bound), edge| {
+                let (a, b) = (lower(&edge), upper(&edge));
+                (lower_bound.inf(&a), upper_bound.sup(&b))
+            },
+        )
+    }
+
+    pub fn perimeter(&self) -> usize {
+        self.edges()
+            .map(|(a, b)| (a.x.abs_diff(b.x)) + (a.y.abs_diff(b.y)))
+            .sum()
+    }
+
+    // https://en.wikipedia.org/wiki/Shoelace_formula
+    fn signed_double_area(&self) -> isize {
+        self.edges().map(|(a, b)| (a.x * b.y) - (a.y * b.x)).sum()
+    }
+
+    pub fn area(&self) -> usize {
+        self.signed_double_area().unsigned_abs() / 2
+    }
+
+    // https://en.m.wikipedia.org/wiki/Pick%27s_theorem
+    // Only holds for simple polygons. A path that doubles back over itself can have less area than
+    // its boundary accounts for, in which case there is no answer.
+    pub fn interior(&self) -> Option<usize> {
+        (self.area() + 1).checked_sub(self.perimeter() / 2)
+    }
+
+    // The interior plus the boundary, but rearranged so that it still counts the cells dug out
+    // when the path doubles back over itself and there is no interior to speak of.
+    pub fn capacity(&self) -> usize {
+        self.area() + self.perimeter() / 2 + 1
+    }
+
+    pub fn winding(&self) -> Winding {
+        if self.signed_double_area() < 0 {
+            Clockwise
+        } else {
+            CounterClockwise
+        }
+    }
+
+    pub fn self_intersections(&self) -> Vec<(usize, usize)> {
+        let edges: Vec<_> = self.edges().collect();
+        let n = edges.len();
+
+        let mut intersections = Vec::new();
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+
+                if let Some((start, end)) = overlap(&edges[i], &edges[j]) {
+                    // Neighboring edges always share a vertex, but should not share anything more.
+                    if !adjacent || start != end {
+                        intersections.push((i, j));
+                    }
+                }
+            }
+        }
+
+        intersections
+    }
+
+    pub fn is_self_intersecting(&self) -> bool {
+        !self.self_intersections().is_empty()
+    }
+
+    // Draws the edges onto a grid covering the bounds, where each edge is drawn with the value
+    // given for its index and everything else is the background.
+    pub fn rasterize<T: Clone>(
+        &self,
+        background: T,
+        mut edge_value: impl FnMut(usize) -> T,
+    ) -> Vec<Vec<T>> {
+        let (lower_bound, upper_bound) = self.bounds();
+
+        let width = (upper_bound.x - lower_bound.x + 1) as usize;
+        let height = (upper_bound.y - lower_bound.y + 1) as usize;
+
+        let mut grid = vec![vec![background; width]; height];
+
+        for (i, edge) in self.edges().enumerate() {
+            let value = edge_value(i);
+            let (a, b) = (lower(&edge), upper(&edge));
+            for y in a.y..=b.y {
+                for x in a.x..=b.x {
+                    // Rows are drawn top to bottom, but up is towards positive y.
+                    let row = (upper_bound.y - y) as usize;
+                    let column = (x - lower_bound.x) as usize;
+                    grid[row][column] = value.clone();
+                }
+            }
+        }
+
+        grid
+    }
+
+    pub fn render_ascii(&self, scale: usize) -> String {
+        let scale = scale.max(1) as isize;
+
+        let scaled: Vec<_> = self
+            .vertices
+            .iter()
+            .map(|p| Point::new(p.x.div_euclid(scale), p.y.div_euclid(scale)))
+            .collect();
+
+        let polygon = Self { vertices: scaled };
+
+        polygon
+            .rasterize('.', |_| '#')
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .chain(std::iter::once('\n'))
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    pub fn render_svg(&self) -> String {
+        let (lower_bound, upper_bound) = self.bounds();
+
+        let width = upper_bound.x - lower_bound.x;
+        let height = upper_bound.y - lower_bound.y;
+
+        let points = self
+            .vertices
+            .iter()
+            .map(|p| format!("{},{}", p.x - lower_bound.x, upper_bound.y - p.y))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            concat!(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -1 {} {}\">\n",
+                "  <polygon points=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"1\" ",
+                "vector-effect=\"non-scaling-stroke\"/>\n",
+                "</svg>\n"
+            ),
+            width + 2,
+            height + 2,
+            points
+        )
+    }
+}
+
+impl std::convert::TryFrom<&[Instruction]> for Polygon {
+    type Error = anyhow::Error;
+
+    fn try_from(instructions: &[Instruction]) -> Result<Self, Self::Error> {
+        ensure!(!instructions.is_empty(), "no instructions");
+
+        let origin = Point::from([0, 0]);
+
+        let mut vertices = vec![origin];
+
+        for instruction in instructions {
+            let vector = instruction.direction.unit() * instruction.distance as isize;
+            vertices.push(vertices[vertices.len() - 1] + vector);
+        }
+
+        let end = vertices.pop().unwrap();
+
+        ensure!(
+            end == origin,
+            "expected path to end where it started at {}, but it ended at {}",
+            origin,
+            end
+        );
+
+        Ok(Self { vertices })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::Direction;
+
+    use super::*;
+
+    use Direction::*;
+
+    fn polygon(steps: &[(Direction, usize)]) -> anyhow::Result<Polygon> {
+        let instructions: Vec<_> = steps
+            .iter()
+            .map(|&(direction, distance)| Instruction {
+                direction,
+                distance,
+                color: Default::default(),
+            })
+            .collect();
+
+        instructions.as_slice().try_into()
+    }
+
+    #[test]
+    fn test_square() -> anyhow::Result<()> {
+        let square = polygon(&[(Right, 2), (Down, 2), (Left, 2), (Up, 2)])?;
+
+        assert_eq!(square.vertices().len(), 4);
+        assert_eq!(square.perimeter(), 8);
+        assert_eq!(square.area(), 4);
+        assert_eq!(square.interior(), Some(1));
+        assert_eq!(square.capacity(), 9);
+        assert_eq!(square.winding(), Clockwise);
+        assert!(!square.is_self_intersecting());
+        assert_eq!(square.render_ascii(1), "###\n#.#\n###\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_winding() -> anyhow::Result<()> {
+        let square = polygon(&[(Up, 1), (Right, 1), (Down, 1), (Left, 1)])?;
+        assert_eq!(square.winding(), Clockwise);
+
+        let square = polygon(&[(Right, 1), (Up, 1), (Left, 1), (Down, 1)])?;
+        assert_eq!(square.winding(), CounterClockwise);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_self_intersections() -> anyhow::Result<()> {
+        // A figure eight crosses itself in the middle.
+        let eight = polygon(&[
+            (Right, 2),
+            (Down, 4),
+            (Right, 2),
+            (Up, 2),
+            (Left, 4),
+            (Up, 2),
+        ])?;
+        assert_eq!(eight.self_intersections(), vec![(1, 4)]);
+
+        // Doubling back over the previous edge overlaps a neighbor.
+        let spike = polygon(&[(Right, 3), (Left, 1), (Down, 1), (Left, 2), (Up, 1)])?;
+        assert_eq!(spike.self_intersections(), vec![(0, 1), (0, 2)]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_degenerate() -> anyhow::Result<()> {
+        let line = polygon(&[(Right, 2), (Left, 2)])?;
+        assert_eq!(line.area(), 0);
+        assert_eq!(line.interior(), None);
+        assert_eq!(line.capacity(), 3);
+
+        let spike = polygon(&[(Right, 3), (Left, 1), (Down, 1), (Left, 2), (Up, 1)])?;
+        assert_eq!(spike.capacity(), 7);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unclosed() {
+        assert!(polygon(&[(Right, 2), (Down, 2)]).is_err());
+    }
+
+    #[test]
+    fn test_render_scaled() -> anyhow::Result<()> {
+        let square = polygon(&[(Right, 10), (Down, 10), (Left, 10), (Up, 10)])?;
+        assert_eq!(square.render_ascii(5), "###\n#.#\n###\n");
+        Ok(())
+    }
+}
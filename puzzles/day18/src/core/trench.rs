@@ -0,0 +1,87 @@
+use super::{Color, Instruction, Point, Polygon};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trench {
+    polygon: Polygon,
+    colors: Vec<Color>,
+}
+
+impl Trench {
+    pub fn polygon(&self) -> &Polygon {
+        &self.polygon
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = ((Point, Point), Color)> + '_ {
+        self.polygon.edges().zip(self.colors.iter().copied())
+    }
+
+    fn pixels(&self) -> Vec<Vec<Option<Color>>> {
+        self.polygon.rasterize(None, |i| Some(self.colors[i]))
+    }
+
+    pub fn render_ppm(&self) -> Vec<u8> {
+        let pixels = self.pixels();
+
+        let height = pixels.len();
+        let width = pixels[0].len();
+
+        let mut output = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+
+        for color in pixels.into_iter().flatten() {
+            output.extend(color.unwrap_or_default().to_bytes());
+        }
+
+        output
+    }
+
+    pub fn render_svg(&self) -> String {
+        let (lower_bound, upper_bound) = self.polygon.bounds();
+
+        let width = upper_bound.x - lower_bound.x;
+        let height = upper_bound.y - lower_bound.y;
+
+        let mut output = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -1 {} {}\">\n",
+            width + 2,
+            height + 2
+        );
+
+        output.push_str(&format!(
+            "  <rect x=\"-1\" y=\"-1\" width=\"{}\" height=\"{}\" fill=\"black\"/>\n",
+            width + 2,
+            height + 2
+        ));
+
+        for ((a, b), color) in self.edges() {
+            output.push_str(&format!(
+                concat!(
+                    "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" ",
+                    "stroke-width=\"1\" stroke-linecap=\"square\"/>\n"
+                ),
+                a.x - lower_bound.x,
+                upper_bound.y - a.y,
+                b.x - lower_bound.x,
+                upper_bound.y - b.y,
+                color
+            ));
+        }
+
+        output.push_str("</svg>\n");
+
+        output
+    }
+}
+
+impl std::convert::TryFrom<&[Instruction]> for Trench {
+    type Error = anyhow::Error;
+
+    fn try_from(instructions: &[Instruction]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            polygon: instructions.try_into()?,
+            colors: instructions
+                .iter()
+                .map(|instruction| instruction.color)
+                .collect(),
+        })
+    }
+}
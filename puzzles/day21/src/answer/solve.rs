@@ -1,6 +1,4 @@
-use anyhow::{ensure, Context};
-
-use crate::core::Graph;
+use crate::core::{DistanceField, Graph};
 
 use super::{Parsed1, Parsed2};
 
@@ -11,194 +9,49 @@ pub type Solution2 = Solution;
 #[cfg(test)]
 const STEPS1: usize = 6;
 
-#[cfg(test)]
-const STEPS2: usize = 6;
-
 #[cfg(not(test))]
 const STEPS1: usize = 64;
 
-#[cfg(not(test))]
 const STEPS2: usize = 26501365;
 
-// The first part is a brute-force flood filling that keeps track of the step parity as it grows.
-// This will be used in part 2 to get solution values at various points for interpolation.
+// The first part measures the distance to every garden within reach and counts the ones with the
+// same parity as the number of steps. Part 2 reads its samples for interpolation the same way.
 
 pub fn solve1(map: &Parsed1) -> anyhow::Result<Solution1> {
-    map.iter_walk_reached_counts()
-        .nth(STEPS1)
-        .context("no solution")
+    Ok(map.distance_field(STEPS1).count_reached(STEPS1))
 }
 
-// The input grid looks like the following, with the rocks naturally clustered into sections:
-//
-//     +---+---+    It has the following qualities:
-//     |E /|\ F|
-//     |/ A|B \|    1. The grid is a square.
-//     +---S---+    2. The start is at the center.
-//     |\ D|C /|    3. The start row/column is completely clear of rocks.
-//     |H \|/ G|    4. There is a diamond shape area clear of rocks
-//     +---+---+
-//
-// The diamond in the center (ABCD) will be referred to as D₀.
-// Since this grid is tiled infinitely in all directions, if you visualize the adjacent grids,
-// you'll notice that there is only one other distinct diamond (GHEF) which I'll call D₁.
-//
-//     +---+---+---+---+---+---+         +---+---+---+---+---+---+
-//     |E /|\ F|E /|\ F|E /|\ F|         |E /|\ F|E / \ F|E /|\ F|
-//     |/ A|B \|/ A|B \|/ A|B \|         |/ A|B \|/     \|/ A|B \|
-//     +---S---+---S---+---S---+         +---S---+   D₀  +---S---+
-//     |\ D|C /|\ D|C /|\ D|C /|         |\ D|C / \     / \ D|C /|
-//     |H \|/ G|H \|/ G|H \|/ G|         |H \|/     \ /     \|/ G|
-//     +---+---+---+---+---+---+         +---+   D₁  +   D₁  +---+
-//     |E /|\ F|E / \ F|E /|\ F|         |E / \     / \     / \ F|
-//     |/ A|B \|/     \|/ A|B \|         |/     \ /     \ /     \|
-//     +---S---+   D₀  +---S---+    =    +   D₀  +   D₀  +   D₀  +
-//     |\ D|C /|\     /|\ D|C /|         |\     / \     / \     /|
-//     |H \|/ G|H \ / G|H \|/ G|         |H \ /     \ /     \ / G|
-//     +---+---+---+---+---+---+         +---+   D₁  +   D₁  +---+
-//     |E /|\ F|E /|\ F|E /|\ F|         |E /|\     / \     /|\ F|
-//     |/ A|B \|/ A|B \|/ A|B \|         |/ A|B \ /     \ / A|B \|
-//     +---S---+---S---+---S---+         +---S---+   D₀  +---S---+
-//     |\ D|C /|\ D|C /|\ D|C /|         |\ D|C /|\     /|\ D|C /|
-//     |H \|/ G|H \|/ G|H \|/ G|         |H \|/ G|H \ / G|H \|/ G|
-//     +---+---+---+---+---+---+         +---+---+---+---+---+---+
-//
-// From visualizing the frontier growth when flood-filling steps:
-//
-//     1. The frontier grows in the rough shape of a diamond (property of flood-filling).
-//     2. After `size/2` steps, the frontier has exactly covered the initial diamond D₀ (presumably
-//        because of qualities 2 and 3 of the grid.
-//     3. After another `size` steps, the frontier has exactly added the 8 adjacent diamonds.
-//     4. Each `size` steps thereafter will add another "layer" of adjacent diamonds that are an
-//        even split of the two types of diamond.
-//
-// Since the diamond is growing equally in all directions and is essentially a big square, its
-// area is going to be the side length squared. This is quadratic growth.
+// Every time the walk crosses another tile in each direction, the diamond it covers gets another
+// layer of tiles, so the number of gardens reached grows quadratically with the number of tiles
+// crossed. Since the grid is tiled infinitely, the tiles line up the same way again after the
+// least common multiple of its height and width, so the counts sampled at that spacing (starting
+// from whatever remainder the target step leaves over) can be fit to a quadratic:
 //
 //     f(x) = ax² + bx + c
 //
-// Given what I've recently learned about interpolation and from what I've seen of the flood-fill
-// visualization, I'm not sure it's possible to find a formula to determine the solution at every
-// possible step, but given that we're trying to find the solution at 26,501,365 steps, and that
-// number can also be represented as 65 + 131 * 202300, this is convenient given what has been
-// observed so far:
-//
-//     1. The width of the grid is 131.
-//     2. The start is at the center (row 65, column 65).
-//     3. After 65 steps, the initial D₀ is exactly covered.
-//     4. After 131 more steps, the next layer of adjacent diamonds is covered.
-//     5. With every additional 131 steps, another layer of adjacent diamonds is added.
-//
-// This suggests that the necessary even spacing of the x axis will be defined like:
-//
-//     65 + i * 131
-//
-// Which gives the first three step numbers to sample:
+// For the puzzle input, the grid is 131 by 131 with the start in the center, and 26,501,365 is
+// 65 + 131 * 202300, so the samples are taken at:
 //
 //     step₀ = 65
 //     step₁ = 65 + 131
 //     step₂ = 65 + 131 * 2
 //
-// And the step number we need a solution for:
-//
-//     stepₙ = 65 + 131 * 202300 = 26501365
+// And f(202300) is the solution.
 //
-// With n = 202300, once we have determined the interpolated f(x), f(n) should give the solution.
+// Nothing about the input's shape is relied on, though. Near the start, the walk can take a few
+// tiles to settle into growing evenly, so the fit is checked against an extra sample and the
+// samples are moved farther out until it holds.
 
 pub fn solve2(map: &Parsed2) -> anyhow::Result<Solution2> {
-    // Ensure grid quality 1.
-    let (height, width) = map.grid.shape();
-    ensure!(height == width, "grid is not a square");
-
-    let half = width / 2;
-
-    // Ensure grid quality 2.
-    let center = (half as isize, half as isize);
-    ensure!(map.start == center, "start is not at the center");
-
-    // Ensure grid quality 3 (for start row).
-    ensure!(
-        map.grid.row(half).iter().all(|tile| tile.is_garden()),
-        "start row is not clear"
-    );
-
-    // Ensure grid quality 3 (for start column).
-    ensure!(
-        map.grid.column(half).iter().all(|tile| tile.is_garden()),
-        "start column is not clear"
-    );
-
-    let mut walk = map.iter_walk_reached_counts().enumerate();
-
-    let mut iter_ys = (0..).map(|i| {
-        let steps = half + i * width;
-        walk.find_map(|(i, reached_count)| (i == steps).then_some(reached_count))
-            .unwrap() as isize
-    });
-
-    // Get 3 values corresponding to f(0), f(1), and f(2).
-    let y0 = iter_ys.next().unwrap();
-    let y1 = iter_ys.next().unwrap();
-    let y2 = iter_ys.next().unwrap();
-
-    let w = width as isize;
-    let n = (STEPS2 as isize - w / 2) / w;
-
-    // Given that f(x) = ax² + bx + c, we can solve the equations:
-    //
-    //     y₀ = ax₀² + bx₀ + c
-    //     y₁ = ax₁² + bx₁ + c
-    //     y₂ = ax₂² + bx₂ + c
-    //
-    // Substituting x₀ = 0, x₁ = 1, and x₂ = 2, these can be simplified to:
-    //
-    //     y₀ = c
-    //     y₁ = a + b + c
-    //     y₂ = 4a + 2b + c
-
-    let c = y0;
-
-    // Substituting y₀ in for c, we only need to solve the following equations:
-    //
-    //     y₁ =  a +  b + y₀
-    //     y₂ = 4a + 2b + y₀
-    //
-    // One way to solve for a is to scale the first equation so that the b's cancel:
-    //
-    //     -2y₁ = -2a - 2b - 2y₀
-    //       y₂ =  4a + 2b +  y₀
-    //
-    // Then adding them together, rearranging and solving for a gets:
-    //
-    //     y₂ - 2y₁ = 2a - y₀
-    //     a = (y₀ -2y₁ + y₂) / 2
-
-    let a = (y0 - 2 * y1 + y2) / 2;
-
-    // In a similar fashion, we can scale the same two equations so that the a's cancel:
-    //
-    //     -4y₁ = -4a - 4b - 4y₀
-    //       y₂ =  4a + 2b +  y₀
-    //
-    // Then adding them together, rearranging and solving for b gets:
-    //
-    //     y₂ - 4y₁ = -2b - 3y₀
-    //     b = (-3y₀ + 4y₁ - y₂) / 2
-
-    let b = (-3 * y0 + 4 * y1 - y2) / 2;
-
-    // Reassembling the quadratic form with the desired value substituted for x:
-
-    let y = a * n.pow(2) + b * n + c;
-
-    Ok(y as usize)
+    map.interpolate_reached(STEPS2)
 }
 
 #[cfg(test)]
 mod tests {
     use aoc::Input;
 
-    use crate::answer::parse1;
+    use crate::answer::{parse1, parse2};
+    use crate::core::{Grid, MapInfinite, Tile, TiledDistances};
 
     use super::*;
 
@@ -209,4 +62,103 @@ mod tests {
         assert_eq!(solve1(&parse1(INPUT)?)?, 16);
         Ok(())
     }
+
+    #[test]
+    fn test_distance_field() -> anyhow::Result<()> {
+        let walled = parse1(INPUT)?;
+        let infinite = parse2(INPUT)?;
+
+        let walled_field = walled.distance_field(usize::MAX);
+        let infinite_field = infinite.distance_field(50);
+
+        assert_eq!(walled_field.distance(walled.start), Some(0));
+        assert_eq!(walled_field.distance((1, 5)), None);
+        assert_eq!(infinite_field.distance((4, 5)), Some(1));
+        assert_eq!(infinite_field.distance((5, 6)), None);
+        assert_eq!(infinite_field.distance((-100, 0)), None);
+
+        let walled_counts = walled_field.reached_counts(50);
+        let infinite_counts = infinite_field.reached_counts(50);
+
+        for (steps, (walled_expected, infinite_expected)) in walled
+            .iter_walk_reached_counts()
+            .zip(infinite.iter_walk_reached_counts())
+            .take(51)
+            .enumerate()
+        {
+            assert_eq!(walled_field.count_reached(steps), walled_expected);
+            assert_eq!(infinite_field.count_reached(steps), infinite_expected);
+            assert_eq!(walled_counts[steps], walled_expected);
+            assert_eq!(infinite_counts[steps], infinite_expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_reached() -> anyhow::Result<()> {
+        let maps = [
+            // The example has rocks along the start row.
+            INPUT,
+            // Grids do not need to be square.
+            ".......\n.#.#.#.\n...S...\n.##.#..\n.......\n",
+            // The start does not need to be in the center, and its row and column can be blocked.
+            "..#....\n.......\n.#S#...\n...#.#.\n",
+            // Some gardens can never be reached.
+            ".#...\n#.#..\n.#S..\n.....\n",
+            // Without any clear row, moving vertically can be done for free while zigzagging
+            // horizontally around the rocks, up until the vertical distance catches up.
+            "..#....\n.#..#..\nS..#...\n...#.#.\n",
+        ];
+
+        for input in maps {
+            let map = parse2(input)?;
+            let tiled = TiledDistances::new(&map)?;
+
+            for (steps, expected) in map.iter_walk_reached_counts().take(80).enumerate() {
+                assert_eq!(tiled.count_reached(steps), expected, "{} steps", steps);
+            }
+        }
+
+        // Large maps are refused before measuring their distances would take too much memory.
+        let map = MapInfinite {
+            grid: Grid::from_element(400, 400, Tile::Garden),
+            start: (200, 200),
+        };
+        assert!(map.count_reached(100).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve2() -> anyhow::Result<()> {
+        let map = parse2(INPUT)?;
+        assert_eq!(solve2(&map)?, map.count_reached(STEPS2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interpolate_reached() -> anyhow::Result<()> {
+        let maps = [
+            INPUT,
+            ".......\n.#.#.#.\n...S...\n.##.#..\n.......\n",
+            "..#....\n.......\n.#S#...\n...#.#.\n",
+            "..#....\n.#..#..\nS..#...\n...#.#.\n",
+        ];
+
+        for input in maps {
+            let map = parse2(input)?;
+
+            for steps in [0, 6, 10, 50, 100, 500, 1000, 5000, 26501365] {
+                assert_eq!(
+                    map.interpolate_reached(steps)?,
+                    map.count_reached(steps)?,
+                    "{} steps",
+                    steps
+                );
+            }
+        }
+
+        Ok(())
+    }
 }
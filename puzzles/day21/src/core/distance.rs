@@ -0,0 +1,142 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use super::{Direction, Graph, Location};
+
+use Direction::*;
+
+pub trait DistanceField<C> {
+    fn distance(&self, location: Location<C>) -> Option<usize>;
+
+    // Records the distance to a location, unless it already has one. Returns whether it was new.
+    fn insert(&mut self, location: Location<C>, distance: usize) -> bool;
+
+    fn distances(&self) -> impl Iterator<Item = usize> + '_;
+
+    fn fill<G: Graph<Component = C>>(mut self, graph: &G, max_steps: usize) -> Self
+    where
+        Self: Sized,
+        C: Copy,
+    {
+        let start = graph.start();
+        self.insert(start, 0);
+
+        let mut frontier = VecDeque::from([(start, 0)]);
+
+        while let Some((location, distance)) = frontier.pop_front() {
+            if distance == max_steps {
+                continue;
+            }
+
+            for direction in [North, East, South, West] {
+                if let Some(adjacent) = graph.neighbor(location, direction) {
+                    if self.insert(adjacent, distance + 1) {
+                        frontier.push_back((adjacent, distance + 1));
+                    }
+                }
+            }
+        }
+
+        self
+    }
+
+    // A location can be reached in exactly some number of steps if it can be reached in fewer
+    // steps of the same parity, since the extra steps can be spent stepping back and forth.
+    fn count_reached(&self, steps: usize) -> usize {
+        self.distances()
+            .filter(|&distance| distance <= steps && distance % 2 == steps % 2)
+            .count()
+    }
+
+    // The reached counts for every step up to `max_steps`.
+    fn reached_counts(&self, max_steps: usize) -> Vec<usize> {
+        let mut counts = vec![0; max_steps + 1];
+
+        for distance in self.distances() {
+            if distance <= max_steps {
+                counts[distance] += 1;
+            }
+        }
+
+        for steps in 2..counts.len() {
+            counts[steps] += counts[steps - 2];
+        }
+
+        counts
+    }
+}
+
+const UNREACHED: u32 = u32::MAX;
+
+// Distances are kept as `u32` to keep large fields small. No distance can be more than the number
+// of locations, so any field that fits in memory fits in that.
+#[derive(Debug, Clone)]
+pub struct DenseDistances {
+    width: usize,
+    distances: Vec<u32>,
+}
+
+impl DenseDistances {
+    pub fn new((height, width): (usize, usize)) -> Self {
+        Self {
+            width,
+            distances: vec![UNREACHED; height * width],
+        }
+    }
+}
+
+impl DistanceField<usize> for DenseDistances {
+    fn distance(&self, (row, column): Location<usize>) -> Option<usize> {
+        if column >= self.width {
+            return None;
+        }
+
+        self.distances
+            .get(row * self.width + column)
+            .filter(|&&distance| distance != UNREACHED)
+            .map(|&distance| distance as usize)
+    }
+
+    fn insert(&mut self, (row, column): Location<usize>, distance: usize) -> bool {
+        let slot = &mut self.distances[row * self.width + column];
+
+        if *slot != UNREACHED {
+            return false;
+        }
+
+        *slot = distance.try_into().expect("distance should fit in a u32");
+        true
+    }
+
+    fn distances(&self) -> impl Iterator<Item = usize> + '_ {
+        self.distances
+            .iter()
+            .filter(|&&distance| distance != UNREACHED)
+            .map(|&distance| distance as usize)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SparseDistances {
+    distances: HashMap<Location<isize>, usize>,
+}
+
+impl DistanceField<isize> for SparseDistances {
+    fn distance(&self, location: Location<isize>) -> Option<usize> {
+        self.distances.get(&location).copied()
+    }
+
+    fn insert(&mut self, location: Location<isize>, distance: usize) -> bool {
+        match self.distances.entry(location) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(distance);
+                true
+            }
+        }
+    }
+
+    fn distances(&self) -> impl Iterator<Item = usize> + '_ {
+        self.distances.values().copied()
+    }
+}
@@ -2,12 +2,13 @@ use std::collections::HashSet;
 use std::fmt::Debug;
 use std::hash::Hash;
 
-use super::{Direction, Location};
+use super::{Direction, DistanceField, Location};
 
 use Direction::*;
 
 pub trait Graph {
     type Component;
+    type Distances: DistanceField<Self::Component>;
 
     fn start(&self) -> Location<Self::Component>;
 
@@ -17,7 +18,10 @@ pub trait Graph {
         direction: Direction,
     ) -> Option<Location<Self::Component>>;
 
-    fn iter_walk(&self) -> IterGraphWalk<Self>
+    // The shortest number of steps to every location within `max_steps` of the start.
+    fn distance_field(&self, max_steps: usize) -> Self::Distances;
+
+    fn iter_walk(&self) -> IterGraphWalk<'_, Self>
     where
         Self: Sized,
     {
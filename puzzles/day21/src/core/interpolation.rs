@@ -0,0 +1,97 @@
+use super::{DistanceField, Graph, MapInfinite};
+
+// How many periods to skip past the start before giving up on the counts settling into a
+// quadratic. Small maps can take a few periods to get past the area around the start, and maps
+// where some direction is free to walk for a while may not settle for much longer than that.
+const MAX_WARMUP: usize = 4;
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+// The quadratic passing through samples at x = 0, 1, and 2. It's in Newton's forward difference
+// form, so it stays in integers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quadratic {
+    y0: i128,
+    d1: i128,
+    d2: i128,
+}
+
+impl Quadratic {
+    pub fn fit([y0, y1, y2]: [i128; 3]) -> Self {
+        // Given that f(x) = ax² + bx + c, the first and second differences are:
+        //
+        //     Δ₁ = y₁ - y₀ = a + b
+        //     Δ₂ = y₂ - 2y₁ + y₀ = 2a
+        //
+        // Which lets f(x) be rewritten without any division that could leave a remainder:
+        //
+        //     f(x) = y₀ + Δ₁x + Δ₂x(x - 1)/2
+        Self {
+            y0,
+            d1: y1 - y0,
+            d2: y2 - 2 * y1 + y0,
+        }
+    }
+
+    pub fn evaluate(&self, x: i128) -> i128 {
+        self.y0 + self.d1 * x + self.d2 * (x * (x - 1) / 2)
+    }
+}
+
+impl MapInfinite {
+    // Every time the walk crosses another tile in each direction, the area of the diamond it
+    // covers grows quadratically. The tiles line up again in both directions after the least
+    // common multiple of the grid's height and width, so the counts sampled at that spacing
+    // should fit a quadratic. When that spacing is odd, the parity of the tiles flips with each
+    // sample, so spacing them twice as far apart is tried as well.
+    pub fn interpolate_reached(&self, steps: usize) -> anyhow::Result<usize> {
+        let (height, width) = self.grid.shape();
+        let period = lcm(height, width);
+
+        // The counts are all read from one distance field, which only has to be measured again
+        // if the samples don't fit and have to be taken farther out.
+        let mut counts = Vec::new();
+
+        for period in [period, 2 * period] {
+            for warmup in 0..MAX_WARMUP {
+                let base = steps % period + warmup * period;
+                let farthest = steps.min(base + 3 * period);
+
+                if counts.len() <= farthest {
+                    counts = self.distance_field(farthest).reached_counts(farthest);
+                }
+
+                // Close enough to the start to just walk there.
+                if steps == farthest {
+                    return Ok(counts[steps]);
+                }
+
+                let [y0, y1, y2, y3] = [0, 1, 2, 3].map(|i| counts[base + i * period] as i128);
+
+                let quadratic = Quadratic::fit([y0, y1, y2]);
+
+                // One more sample than is needed to fit the quadratic, just to make sure it does.
+                if quadratic.evaluate(3) != y3 {
+                    continue;
+                }
+
+                let x = ((steps - base) / period) as i128;
+
+                return Ok(quadratic.evaluate(x).try_into()?);
+            }
+        }
+
+        // The counts never settled, so count them from the shape of the tiled distances instead.
+        self.count_reached(steps)
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Direction, Graph, Grid, Location};
+use super::{DenseDistances, Direction, DistanceField, Graph, Grid, Location, SparseDistances};
 
 fn has_garden_at(grid: &Grid, location: Location<usize>) -> bool {
     grid.get(location).is_some_and(|&tile| tile.is_garden())
@@ -12,6 +12,7 @@ pub struct MapWalled {
 
 impl Graph for MapWalled {
     type Component = usize;
+    type Distances = DenseDistances;
 
     fn start(&self) -> Location<Self::Component> {
         self.start
@@ -31,6 +32,10 @@ impl Graph for MapWalled {
 
         has_garden_at(&self.grid, adjacent).then_some(adjacent)
     }
+
+    fn distance_field(&self, max_steps: usize) -> Self::Distances {
+        DenseDistances::new(self.grid.shape()).fill(self, max_steps)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -41,6 +46,7 @@ pub struct MapInfinite {
 
 impl Graph for MapInfinite {
     type Component = isize;
+    type Distances = SparseDistances;
 
     fn start(&self) -> Location<Self::Component> {
         self.start
@@ -66,4 +72,8 @@ impl Graph for MapInfinite {
         )
         .then_some((row, column))
     }
+
+    fn distance_field(&self, max_steps: usize) -> Self::Distances {
+        SparseDistances::default().fill(self, max_steps)
+    }
 }
@@ -10,5 +10,14 @@ pub use tile::*;
 pub mod graph;
 pub use graph::*;
 
+pub mod distance;
+pub use distance::*;
+
 pub mod map;
 pub use map::*;
+
+pub mod tiles;
+pub use tiles::*;
+
+pub mod interpolation;
+pub use interpolation::*;
@@ -0,0 +1,592 @@
+use anyhow::bail;
+
+use super::{DenseDistances, Direction, DistanceField, Graph, Location, MapInfinite};
+
+const INITIAL_RADIUS: usize = 5;
+const MAX_RADIUS: usize = 25;
+
+// The most locations to measure at once, which is 64 MiB of distances.
+const MAX_LOCATIONS: usize = 1 << 24;
+
+type Tile = (isize, isize);
+
+// Sums floor((a * i + b) / m) for every i in 0..n.
+// https://atcoder.github.io/ac-library/production/document_en/math.html
+fn floor_sum(mut n: usize, mut m: usize, mut a: usize, mut b: usize) -> usize {
+    let mut sum = 0;
+
+    loop {
+        if a >= m {
+            sum += n * (n - 1) / 2 * (a / m);
+            a %= m;
+        }
+
+        if b >= m {
+            sum += n * (b / m);
+            b %= m;
+        }
+
+        let y_max = a * n + b;
+        if y_max < m {
+            break;
+        }
+
+        n = y_max / m;
+        b = y_max % m;
+        std::mem::swap(&mut m, &mut a);
+    }
+
+    sum
+}
+
+// A half-plane, where a * x + b * y <= limit.
+type Constraint = (usize, usize, usize);
+
+// Counts the points (x, y), both non-negative, that are inside every half-plane. At least one of
+// them needs a > 0 and one needs b > 0, so that there are only so many points.
+fn count_polygon(constraints: &[Constraint]) -> usize {
+    let y_max = constraints
+        .iter()
+        .filter(|&&(_, b, _)| b > 0)
+        .map(|&(_, b, limit)| limit / b)
+        .min()
+        .unwrap();
+
+    let bounds: Vec<_> = constraints
+        .iter()
+        .filter(|&&(a, _, _)| a > 0)
+        .copied()
+        .collect();
+
+    // For each y, x can go as far as the lowest of the bounds. Which one that is only changes
+    // where two of them cross, so the rows are split up there.
+    let mut starts = vec![0];
+
+    for (i, &(a1, b1, limit1)) in bounds.iter().enumerate() {
+        for &(a2, b2, limit2) in &bounds[i + 1..] {
+            let mut numerator = limit1 as i128 * a2 as i128 - limit2 as i128 * a1 as i128;
+            let mut denominator = b1 as i128 * a2 as i128 - b2 as i128 * a1 as i128;
+
+            if denominator == 0 {
+                continue;
+            }
+
+            if denominator < 0 {
+                numerator = -numerator;
+                denominator = -denominator;
+            }
+
+            let start = numerator.div_euclid(denominator) + 1;
+
+            if (1..=y_max as i128).contains(&start) {
+                starts.push(start as usize);
+            }
+        }
+    }
+
+    starts.sort_unstable();
+    starts.dedup();
+
+    let mut count = 0;
+
+    for (i, &start) in starts.iter().enumerate() {
+        let end = starts.get(i + 1).map_or(y_max, |&next| next - 1);
+
+        let &(a, b, limit) = bounds
+            .iter()
+            .min_by(|&&(a1, b1, limit1), &&(a2, b2, limit2)| {
+                ((limit1 - b1 * start) * a2).cmp(&((limit2 - b2 * start) * a1))
+            })
+            .unwrap();
+
+        let n = end - start + 1;
+        count += n + floor_sum(n, a, b, limit - b * end);
+    }
+
+    count
+}
+
+fn is_reached(distance: usize, steps: usize) -> bool {
+    distance <= steps && distance % 2 == steps % 2
+}
+
+// Counts the tiles in a line, each one `period` steps farther than the last, that are reached.
+fn count_line(distance: usize, period: usize, steps: usize) -> usize {
+    if distance > steps {
+        return 0;
+    }
+
+    let remaining = steps - distance;
+    let n = remaining / period;
+
+    if period.is_multiple_of(2) {
+        if remaining.is_multiple_of(2) {
+            n + 1
+        } else {
+            0
+        }
+    } else if remaining.is_multiple_of(2) {
+        n / 2 + 1
+    } else {
+        n.div_ceil(2)
+    }
+}
+
+// According to one piece of a quadrant, the tile `x` tiles farther out vertically and `y` tiles
+// farther out horizontally than its inside corner is `distance + x * vertical + y * horizontal`
+// steps away. Pieces only hold far from the start, so near the corner this can even be negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    distance: isize,
+    vertical: usize,
+    horizontal: usize,
+}
+
+impl Piece {
+    fn distance(&self, x: usize, y: usize) -> isize {
+        self.distance + (x * self.vertical + y * self.horizontal) as isize
+    }
+}
+
+// The farthest that any of the pieces says the tile is.
+fn plane_distance(pieces: &[Piece], x: usize, y: usize) -> isize {
+    pieces
+        .iter()
+        .map(|piece| piece.distance(x, y))
+        .max()
+        .unwrap()
+}
+
+// Counts the tiles in a quadrant that are reached, except for the `skip` by `skip` tiles in its
+// inside corner.
+fn count_plane(pieces: &[Piece], skip: usize, steps: usize) -> usize {
+    // Every step moves between the two colors of a checkerboard, so all of the pieces agree on
+    // which tiles have the right parity.
+    let parity = pieces[0];
+
+    // Splitting the tile offsets by parity means every remaining move is a multiple of two, which
+    // leaves the parity of the total to be decided by the split alone.
+    let mut count = 0;
+
+    for i in 0..2 {
+        for j in 0..2 {
+            if (parity.distance(i, j) - steps as isize).rem_euclid(2) != 0 {
+                continue;
+            }
+
+            let constraints: Option<Vec<_>> = pieces
+                .iter()
+                .map(|piece| {
+                    let limit = usize::try_from(steps as isize - piece.distance(i, j)).ok()?;
+                    Some((2 * piece.vertical, 2 * piece.horizontal, limit))
+                })
+                .collect();
+
+            if let Some(constraints) = constraints {
+                count += count_polygon(&constraints);
+            }
+        }
+    }
+
+    let skipped = (0..skip)
+        .flat_map(|x| (0..skip).map(move |y| (x, y)))
+        .filter(|&(x, y)| {
+            usize::try_from(plane_distance(pieces, x, y))
+                .is_ok_and(|distance| is_reached(distance, steps))
+        })
+        .count();
+
+    count - skipped
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Extension {
+    Line { distance: usize, period: usize },
+    Plane { pieces: Vec<Piece>, skip: usize },
+}
+
+impl Extension {
+    fn count_reached(&self, steps: usize) -> usize {
+        match self {
+            &Self::Line { distance, period } => count_line(distance, period, steps),
+            Self::Plane { pieces, skip } => count_plane(pieces, *skip, steps),
+        }
+    }
+}
+
+// The tiles within `radius` of the start's tile, as one bounded grid.
+#[derive(Debug, Clone)]
+struct TiledMap<'a> {
+    map: &'a MapInfinite,
+    radius: usize,
+}
+
+impl TiledMap<'_> {
+    fn shape(&self) -> (usize, usize) {
+        let (height, width) = self.map.grid.shape();
+        let tiles = 2 * self.radius + 1;
+        (tiles * height, tiles * width)
+    }
+}
+
+impl Graph for TiledMap<'_> {
+    type Component = usize;
+    type Distances = DenseDistances;
+
+    fn start(&self) -> Location<Self::Component> {
+        let (height, width) = self.map.grid.shape();
+
+        (
+            self.radius * height + self.map.start.0.rem_euclid(height as isize) as usize,
+            self.radius * width + self.map.start.1.rem_euclid(width as isize) as usize,
+        )
+    }
+
+    fn neighbor(
+        &self,
+        (row, column): Location<Self::Component>,
+        direction: Direction,
+    ) -> Option<Location<Self::Component>> {
+        let (height, width) = self.map.grid.shape();
+        let (rows, columns) = self.shape();
+        let (vertical, horizontal) = direction.step();
+
+        let adjacent = (
+            row.checked_add_signed(vertical)?,
+            column.checked_add_signed(horizontal)?,
+        );
+
+        (adjacent.0 < rows
+            && adjacent.1 < columns
+            && self.map.grid[(adjacent.0 % height, adjacent.1 % width)].is_garden())
+        .then_some(adjacent)
+    }
+
+    fn distance_field(&self, max_steps: usize) -> Self::Distances {
+        DenseDistances::new(self.shape()).fill(self, max_steps)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TiledDistances {
+    height: usize,
+    width: usize,
+    radius: usize,
+    distances: DenseDistances,
+    extensions: Vec<Extension>,
+}
+
+impl TiledDistances {
+    fn walk(map: &MapInfinite, radius: usize) -> Self {
+        let (height, width) = map.grid.shape();
+
+        Self {
+            height,
+            width,
+            radius,
+            distances: TiledMap { map, radius }.distance_field(usize::MAX),
+            extensions: Vec::new(),
+        }
+    }
+
+    pub fn new(map: &MapInfinite) -> anyhow::Result<Self> {
+        let (height, width) = map.grid.shape();
+        let mut radius = INITIAL_RADIUS;
+
+        loop {
+            let locations = (2 * radius + 1).pow(2) * height * width;
+
+            if locations > MAX_LOCATIONS {
+                bail!(
+                    "measuring {} tiles around the start would take {} locations, but the limit is {}",
+                    radius,
+                    locations,
+                    MAX_LOCATIONS
+                );
+            }
+
+            let mut tiled = Self::walk(map, radius);
+
+            if let Some(extensions) = tiled.extensions() {
+                tiled.extensions = extensions;
+                return Ok(tiled);
+            }
+
+            radius += 4;
+
+            if radius > MAX_RADIUS {
+                bail!(
+                    "distances did not settle into a repeating pattern within {} tiles of the start",
+                    MAX_RADIUS
+                );
+            }
+        }
+    }
+
+    // Tiles are only measured out to the radius, but any paths that would have left and come
+    // back in are cut off, so only trust tiles that are a safe distance from the edge.
+    fn reach(&self) -> isize {
+        self.radius as isize - 2
+    }
+
+    pub fn distance(
+        &self,
+        (tile_row, tile_column): Tile,
+        (row, column): (usize, usize),
+    ) -> Option<usize> {
+        let radius = self.radius as isize;
+
+        if tile_row.abs() > radius
+            || tile_column.abs() > radius
+            || row >= self.height
+            || column >= self.width
+        {
+            return None;
+        }
+
+        let row = (tile_row + self.radius as isize) as usize * self.height + row;
+        let column = (tile_column + self.radius as isize) as usize * self.width + column;
+
+        self.distances.distance((row, column))
+    }
+
+    // Far enough away from the start, moving another tile in the same direction should always
+    // cost the same number of steps. If so, this is that number.
+    fn period(&self, tile: Tile, direction: Tile, cell: (usize, usize)) -> Option<usize> {
+        let prev = (tile.0 - direction.0, tile.1 - direction.1);
+        let prev_prev = (prev.0 - direction.0, prev.1 - direction.1);
+
+        match (
+            self.distance(tile, cell),
+            self.distance(prev, cell),
+            self.distance(prev_prev, cell),
+        ) {
+            (Some(a), Some(b), Some(c)) => (a > b && b > c && a - b == b - c).then_some(a - b),
+            // Gardens that are walled off are never reached, no matter which tile they're in.
+            (None, None, None) => Some(1),
+            _ => None,
+        }
+    }
+
+    // Far enough away from the start, a quadrant splits into wedges, and moving another tile in
+    // the same direction always costs the same number of steps within each one. That cost can be
+    // nothing, like when moving one way can be done for free while zigzagging the other way. The
+    // distance to a tile is the most that any of the wedges says it is.
+    //
+    // Each wedge is found where its costs hold for a few tiles in a row, and then they're all
+    // checked against the outer half of the measured tiles in the quadrant, which keeps the quirks
+    // of the tiles around the start from having to be fit too.
+    fn pieces(&self, (vertical, horizontal): Tile, cell: (usize, usize)) -> Option<Vec<Piece>> {
+        let k = self.reach();
+        let margin = self.margin();
+
+        let size = (k - margin) as usize;
+        let inner = (k / 2 - margin - 1).max(0) as usize;
+
+        let tile = |x: usize, y: usize| {
+            (
+                vertical * (margin + 1 + x as isize),
+                horizontal * (margin + 1 + y as isize),
+            )
+        };
+        let tiles = || {
+            (0..size)
+                .flat_map(|x| (0..size).map(move |y| (x, y)))
+                .filter(|&(x, y)| x.max(y) >= inner)
+        };
+
+        // Gardens that are walled off are never reached, no matter which tile they're in.
+        if tiles().all(|(x, y)| self.distance(tile(x, y), cell).is_none()) {
+            return Some(Vec::new());
+        }
+
+        let mut distances = vec![vec![0; size]; size];
+
+        for (x, y) in tiles() {
+            distances[x][y] = self.distance(tile(x, y), cell)? as isize;
+        }
+
+        let d = |x: usize, y: usize| distances[x][y];
+
+        let mut costs = Vec::new();
+
+        // Every tile used has to be in the outer half too.
+        for (x, y) in tiles()
+            .filter(|&(x, y)| x >= 2 && y >= 2 && (x - 2).max(y) >= inner && x.max(y - 2) >= inner)
+        {
+            let vertical = d(x, y) - d(x - 1, y);
+            let horizontal = d(x, y) - d(x, y - 1);
+
+            if vertical >= 0
+                && horizontal >= 0
+                && vertical == d(x - 1, y) - d(x - 2, y)
+                && horizontal == d(x, y - 1) - d(x, y - 2)
+                && !costs.contains(&(vertical as usize, horizontal as usize))
+            {
+                costs.push((vertical as usize, horizontal as usize));
+            }
+        }
+
+        let pieces: Vec<_> = costs
+            .into_iter()
+            .map(|(vertical, horizontal)| {
+                let distance = tiles()
+                    .map(|(x, y)| d(x, y) - (x * vertical + y * horizontal) as isize)
+                    .min()
+                    .unwrap();
+
+                Piece {
+                    distance,
+                    vertical,
+                    horizontal,
+                }
+            })
+            .collect();
+
+        // Without some cost in both directions, there would be no end to the tiles reached.
+        let bounded = pieces.iter().any(|piece| piece.vertical > 0)
+            && pieces.iter().any(|piece| piece.horizontal > 0);
+
+        (bounded && tiles().all(|(x, y)| plane_distance(&pieces, x, y) == d(x, y)))
+            .then_some(pieces)
+    }
+
+    // Tiles close to an axis can be quicker to get to than the rest of their quadrant, like when
+    // the start's row is clear, so the ones within this many tiles of it are kept apart.
+    fn margin(&self) -> isize {
+        self.reach() / 4
+    }
+
+    // The tiles outside of the measured area are grouped into lines going straight out along each
+    // axis, and quadrants filling the space in between. Lines are extended from their first tile
+    // outside, assuming the steps needed grow by the same amount for every tile added. Quadrants
+    // are extended from their inside corner by the pieces they split into, leaving out the tiles
+    // that were already measured.
+    fn extensions(&self) -> Option<Vec<Extension>> {
+        let k = self.reach();
+        let margin = self.margin();
+
+        let mut extensions = Vec::new();
+
+        for row in 0..self.height {
+            for column in 0..self.width {
+                let cell = (row, column);
+
+                for (vertical, horizontal) in [(-1isize, 0isize), (1, 0), (0, -1), (0, 1)] {
+                    for t in -margin..=margin {
+                        let tile = (
+                            vertical * k + horizontal.abs() * t,
+                            horizontal * k + vertical.abs() * t,
+                        );
+
+                        let period = self.period(tile, (vertical, horizontal), cell)?;
+
+                        if let Some(distance) = self.distance(tile, cell) {
+                            extensions.push(Extension::Line { distance, period });
+                        }
+                    }
+                }
+
+                for vertical in [-1, 1] {
+                    for horizontal in [-1, 1] {
+                        let pieces = self.pieces((vertical, horizontal), cell)?;
+
+                        if !pieces.is_empty() {
+                            extensions.push(Extension::Plane {
+                                pieces,
+                                skip: (k - 1 - margin) as usize,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(extensions)
+    }
+
+    pub fn count_reached(&self, steps: usize) -> usize {
+        let k = self.reach();
+
+        let mut count = 0;
+
+        for row in 0..self.height {
+            for column in 0..self.width {
+                for tile_row in -(k - 1)..=(k - 1) {
+                    for tile_column in -(k - 1)..=(k - 1) {
+                        if self
+                            .distance((tile_row, tile_column), (row, column))
+                            .is_some_and(|distance| is_reached(distance, steps))
+                        {
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        count
+            + self
+                .extensions
+                .iter()
+                .map(|extension| extension.count_reached(steps))
+                .sum::<usize>()
+    }
+}
+
+impl MapInfinite {
+    pub fn count_reached(&self, steps: usize) -> anyhow::Result<usize> {
+        Ok(TiledDistances::new(self)?.count_reached(steps))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Grid, Tile};
+
+    use super::*;
+
+    #[test]
+    fn test_count_polygon() {
+        let brute_force = |constraints: &[Constraint]| {
+            (0..=30)
+                .flat_map(|x| (0..=30).map(move |y| (x, y)))
+                .filter(|&(x, y)| {
+                    constraints
+                        .iter()
+                        .all(|&(a, b, limit)| a * x + b * y <= limit)
+                })
+                .count()
+        };
+
+        for limit in 0..30 {
+            for a in 0..6 {
+                for b in 1..6 {
+                    let constraints = [(a, b, limit), (3, 1, limit + 2), (1, 0, 17)];
+                    assert_eq!(count_polygon(&constraints), brute_force(&constraints));
+
+                    let constraints = [(b, a, limit), (4, 7, 2 * limit)];
+                    assert_eq!(count_polygon(&constraints), brute_force(&constraints));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_distance() -> anyhow::Result<()> {
+        let map = MapInfinite {
+            grid: Grid::from_element(3, 3, Tile::Garden),
+            start: (1, 1),
+        };
+
+        let tiled = TiledDistances::new(&map)?;
+
+        assert_eq!(tiled.distance((0, 0), (1, 1)), Some(0));
+        assert_eq!(tiled.distance((-1, 2), (1, 1)), Some(9));
+        assert_eq!(tiled.distance((-100, 0), (0, 0)), None);
+        assert_eq!(tiled.distance((0, 100), (0, 0)), None);
+        assert_eq!(tiled.distance((0, 0), (3, 0)), None);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::{HashSet, VecDeque};
+use crate::core::SupportGraph;
 
 use super::{Parsed1, Parsed2};
 
@@ -29,12 +29,44 @@ pub fn solve2(snapshot: &Parsed2) -> anyhow::Result<Solution2> {
     let mut stack = snapshot.clone();
     stack.settle();
 
-    let (supports, is_supported_by) = stack.dependencies();
+    Ok(SupportGraph::from(&stack).total_falls())
+}
 
-    Ok(stack
-        .bricks
-        .iter()
-        .map(|start| {
+#[cfg(test)]
+mod tests {
+    use aoc::Input;
+
+    use std::collections::{HashSet, VecDeque};
+
+    use crate::answer::{parse1, parse2};
+    use crate::core::Axis;
+
+    use super::*;
+
+    const INPUT: Input = include_str!("../../input-test");
+
+    #[test]
+    fn test_solve1() -> anyhow::Result<()> {
+        assert_eq!(solve1(&parse1(INPUT)?)?, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve2() -> anyhow::Result<()> {
+        assert_eq!(solve2(&parse2(INPUT)?)?, 7);
+        Ok(())
+    }
+
+    #[test]
+    fn test_support_graph() -> anyhow::Result<()> {
+        let mut stack = parse2(INPUT)?;
+        stack.settle();
+
+        let graph = SupportGraph::from(&stack);
+
+        // Removing a brick drops everything whose only chain of support runs through it.
+        let (supports, is_supported_by) = stack.dependencies();
+        for (i, start) in stack.bricks.iter().enumerate() {
             let mut frontier = VecDeque::from([start]);
             let mut dropping = HashSet::new();
 
@@ -50,30 +82,95 @@ pub fn solve2(snapshot: &Parsed2) -> anyhow::Result<Solution2> {
                 }
             }
 
-            dropping.len() - 1
-        })
-        .sum())
-}
-
-#[cfg(test)]
-mod tests {
-    use aoc::Input;
-
-    use crate::answer::{parse1, parse2};
+            assert_eq!(graph.falls(i), dropping.len() - 1, "brick number {}", i + 1);
+        }
 
-    use super::*;
+        assert_eq!(graph.dominator(0), None);
+        assert_eq!(graph.dominator(6), Some(5));
+        assert_eq!(
+            graph.single_points_of_failure(usize::MAX),
+            vec![(0, 6), (5, 1)]
+        );
+        assert_eq!(graph.single_points_of_failure(1), vec![(0, 6)]);
 
-    const INPUT: Input = include_str!("../../input-test");
+        Ok(())
+    }
 
     #[test]
-    fn test_solve1() -> anyhow::Result<()> {
-        assert_eq!(solve1(&parse1(INPUT)?)?, 5);
+    fn test_render_projection() -> anyhow::Result<()> {
+        let mut stack = parse2(INPUT)?;
+
+        assert_eq!(
+            stack.render_projection(Axis::X),
+            concat!(
+                " x\n",
+                "012\n",
+                ".G. 9\n",
+                ".G. 8\n",
+                "... 7\n",
+                "FFF 6\n",
+                "..E 5 z\n",
+                "D.. 4\n",
+                "CCC 3\n",
+                "BBB 2\n",
+                ".A. 1\n",
+                "--- 0\n",
+            )
+        );
+
+        assert_eq!(
+            stack.render_projection(Axis::Y),
+            concat!(
+                " y\n",
+                "012\n",
+                ".G. 9\n",
+                ".G. 8\n",
+                "... 7\n",
+                ".F. 6\n",
+                "EEE 5 z\n",
+                "DDD 4\n",
+                "..C 3\n",
+                "B.. 2\n",
+                "AAA 1\n",
+                "--- 0\n",
+            )
+        );
+
+        stack.settle();
+
+        assert_eq!(
+            stack.render_projection(Axis::X),
+            concat!(
+                " x\n",
+                "012\n",
+                ".G. 6\n",
+                ".G. 5\n",
+                "FFF 4\n",
+                "D.E 3 z\n",
+                "??? 2\n",
+                ".A. 1\n",
+                "--- 0\n",
+            )
+        );
+
         Ok(())
     }
 
     #[test]
-    fn test_solve2() -> anyhow::Result<()> {
-        assert_eq!(solve2(&parse2(INPUT)?)?, 7);
+    fn test_render_ply() -> anyhow::Result<()> {
+        let mut stack = parse2(INPUT)?;
+        stack.settle();
+
+        let ply = stack.render_ply();
+        let mut lines = ply.lines();
+
+        assert!(lines.any(|line| line == "element vertex 160"));
+        assert!(lines.any(|line| line == "element face 120"));
+        assert!(lines.any(|line| line == "end_header"));
+
+        // The first brick brings down everything else, so it is the hottest.
+        assert_eq!(lines.next(), Some("1 0 1 255 0 0"));
+
         Ok(())
     }
 }
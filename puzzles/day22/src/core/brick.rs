@@ -1,6 +1,6 @@
 use anyhow::Context;
 
-use std::cmp::max;
+use std::cmp::{max, min};
 
 use super::{Location, Range};
 
@@ -13,13 +13,22 @@ pub struct Brick {
 
 impl Brick {
     pub fn new(start: Location, end: Location) -> Self {
+        // Endpoints can be given in either order, but ranges always go from low to high.
         Self {
-            x: (start.x, end.x).into(),
-            y: (start.y, end.y).into(),
-            z: (start.z, end.z).into(),
+            x: (min(start.x, end.x), max(start.x, end.x)).into(),
+            y: (min(start.y, end.y), max(start.y, end.y)).into(),
+            z: (min(start.z, end.z), max(start.z, end.z)).into(),
         }
     }
 
+    pub fn is_straight(&self) -> bool {
+        [self.x, self.y, self.z]
+            .into_iter()
+            .filter(|range| range.size() > 1)
+            .count()
+            <= 1
+    }
+
     pub fn start(&self) -> Location {
         Location::new(self.x.start, self.y.start, self.z.start)
     }
@@ -51,7 +60,7 @@ impl Brick {
         .take(self.size())
     }
 
-    fn iter_xy(&self) -> impl Iterator<Item = (usize, usize)> {
+    pub fn iter_xy(&self) -> impl Iterator<Item = (usize, usize)> {
         let len = if self.z.size() == 1 {
             max(self.x.size(), self.y.size())
         } else {
@@ -120,4 +129,14 @@ mod tests {
             test!((0, 0, 0), (0, 0, end), [(0, 0)]);
         }
     }
+
+    #[test]
+    fn test_reversed() {
+        let brick = Brick::new((2, 0, 5).into(), (0, 0, 5).into());
+        assert_eq!(brick, Brick::new((0, 0, 5).into(), (2, 0, 5).into()));
+        assert_eq!(brick.locations().count(), 3);
+        assert!(brick.is_straight());
+
+        assert!(!Brick::new((0, 0, 1).into(), (1, 1, 1).into()).is_straight());
+    }
 }
@@ -0,0 +1,195 @@
+use std::collections::{HashMap, HashSet};
+
+use super::{Brick, Range, Stack, SupportGraph, FLOOR};
+
+use Axis::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    X,
+    Y,
+}
+
+impl Axis {
+    fn name(&self) -> char {
+        match self {
+            X => 'x',
+            Y => 'y',
+        }
+    }
+
+    fn range(&self, brick: &Brick) -> Range {
+        match self {
+            X => brick.x,
+            Y => brick.y,
+        }
+    }
+}
+
+fn label(i: usize) -> char {
+    if i < 26 {
+        (b'A' + i as u8) as char
+    } else {
+        '#'
+    }
+}
+
+// The corners of a unit cube, followed by its faces wound counter-clockwise from the outside.
+const CUBE_VERTICES: [(usize, usize, usize); 8] = [
+    (0, 0, 0),
+    (1, 0, 0),
+    (1, 1, 0),
+    (0, 1, 0),
+    (0, 0, 1),
+    (1, 0, 1),
+    (1, 1, 1),
+    (0, 1, 1),
+];
+
+const CUBE_FACES: [[usize; 4]; 6] = [
+    [0, 3, 2, 1],
+    [4, 5, 6, 7],
+    [0, 1, 5, 4],
+    [2, 3, 7, 6],
+    [0, 4, 7, 3],
+    [1, 2, 6, 5],
+];
+
+fn heat(value: usize, max: usize) -> [u8; 3] {
+    let t = if max == 0 {
+        0.0
+    } else {
+        value as f64 / max as f64
+    };
+
+    [
+        (255.0 * t).round() as u8,
+        0,
+        (255.0 * (1.0 - t)).round() as u8,
+    ]
+}
+
+impl Stack {
+    pub fn render_projection(&self, axis: Axis) -> String {
+        let width = self
+            .bricks
+            .iter()
+            .map(|brick| axis.range(brick).end + 1)
+            .max()
+            .unwrap_or_default();
+
+        let height = self
+            .bricks
+            .iter()
+            .map(|brick| brick.z.end)
+            .max()
+            .unwrap_or_default();
+
+        let mut cells: HashMap<(usize, usize), HashSet<usize>> = HashMap::new();
+
+        for (i, brick) in self.bricks.iter().enumerate() {
+            for h in axis.range(brick).iter() {
+                for z in brick.z.iter() {
+                    cells.entry((h, z)).or_default().insert(i);
+                }
+            }
+        }
+
+        let mut output = String::new();
+
+        output.push_str(&" ".repeat(width / 2));
+        output.push(axis.name());
+        output.push('\n');
+
+        for h in 0..width {
+            output.push_str(&(h % 10).to_string());
+        }
+        output.push('\n');
+
+        for z in (FLOOR..=height).rev() {
+            for h in 0..width {
+                output.push(match cells.get(&(h, z)) {
+                    None => '.',
+                    Some(bricks) if bricks.len() > 1 => '?',
+                    Some(bricks) => label(*bricks.iter().next().unwrap()),
+                });
+            }
+
+            output.push_str(&format!(" {}", z));
+
+            if z == height.div_ceil(2) {
+                output.push_str(" z");
+            }
+
+            output.push('\n');
+        }
+
+        output.push_str(&"-".repeat(width));
+        output.push_str(&format!(" {}\n", FLOOR - 1));
+
+        output
+    }
+
+    pub fn render_ply(&self) -> String {
+        let graph = SupportGraph::from(self);
+
+        let max_falls = (0..graph.len())
+            .map(|i| graph.falls(i))
+            .max()
+            .unwrap_or_default();
+
+        let cells: Vec<_> = self
+            .bricks
+            .iter()
+            .enumerate()
+            .flat_map(|(i, brick)| {
+                let color = heat(graph.falls(i), max_falls);
+                brick.locations().map(move |location| (location, color))
+            })
+            .collect();
+
+        let mut output = String::new();
+
+        output.push_str("ply\nformat ascii 1.0\n");
+        output.push_str(&format!(
+            "element vertex {}\n",
+            cells.len() * CUBE_VERTICES.len()
+        ));
+        output.push_str("property float x\nproperty float y\nproperty float z\n");
+        output.push_str("property uchar red\nproperty uchar green\nproperty uchar blue\n");
+        output.push_str(&format!(
+            "element face {}\n",
+            cells.len() * CUBE_FACES.len()
+        ));
+        output.push_str("property list uchar int vertex_indices\nend_header\n");
+
+        for (location, [red, green, blue]) in cells.iter() {
+            for (dx, dy, dz) in CUBE_VERTICES {
+                output.push_str(&format!(
+                    "{} {} {} {} {} {}\n",
+                    location.x + dx,
+                    location.y + dy,
+                    location.z + dz,
+                    red,
+                    green,
+                    blue
+                ));
+            }
+        }
+
+        for i in 0..cells.len() {
+            let offset = i * CUBE_VERTICES.len();
+            for [a, b, c, d] in CUBE_FACES {
+                output.push_str(&format!(
+                    "4 {} {} {} {}\n",
+                    offset + a,
+                    offset + b,
+                    offset + c,
+                    offset + d
+                ));
+            }
+        }
+
+        output
+    }
+}
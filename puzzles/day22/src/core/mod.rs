@@ -9,3 +9,9 @@ pub use brick::*;
 
 pub mod stack;
 pub use stack::*;
+
+pub mod support;
+pub use support::*;
+
+pub mod export;
+pub use export::*;
@@ -4,23 +4,102 @@ use std::collections::{HashMap, HashSet};
 
 use super::{Brick, Location};
 
-const FLOOR: usize = 1;
+pub const FLOOR: usize = 1;
 
 type Dependencies<'a> = HashMap<&'a Brick, HashSet<&'a Brick>>;
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    Diagonal {
+        brick: usize,
+    },
+    BelowFloor {
+        brick: usize,
+        z: usize,
+    },
+    Overlap {
+        first: usize,
+        second: usize,
+        location: Location,
+    },
+}
+
+impl std::fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Diagonal { brick } => {
+                write!(f, "brick number {} spans more than one axis", brick + 1)
+            }
+            Self::BelowFloor { brick, z } => write!(
+                f,
+                "brick number {} is below the floor at z = {}, but the floor is at z = {}",
+                brick + 1,
+                z,
+                FLOOR
+            ),
+            Self::Overlap {
+                first,
+                second,
+                location,
+            } => write!(
+                f,
+                "brick number {} overlaps brick number {} at {}",
+                second + 1,
+                first + 1,
+                location
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+fn validate(bricks: &[Brick]) -> Result<(), SnapshotError> {
+    let mut occupied: HashMap<Location, usize> = HashMap::new();
+
+    for (i, brick) in bricks.iter().enumerate() {
+        if !brick.is_straight() {
+            return Err(SnapshotError::Diagonal { brick: i });
+        }
+
+        if brick.z.start < FLOOR {
+            return Err(SnapshotError::BelowFloor {
+                brick: i,
+                z: brick.z.start,
+            });
+        }
+
+        for location in brick.locations() {
+            if let Some(first) = occupied.insert(location, i) {
+                return Err(SnapshotError::Overlap {
+                    first,
+                    second: i,
+                    location,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Stack {
     pub bricks: Vec<Brick>,
     location_to_brick: HashMap<Location, usize>,
 }
 
+fn index(bricks: &[Brick]) -> HashMap<Location, usize> {
+    bricks
+        .iter()
+        .enumerate()
+        .flat_map(|(i, brick)| brick.locations().map(move |location| (location, i)))
+        .collect()
+}
+
 impl Stack {
     pub fn new(bricks: Vec<Brick>) -> Self {
-        let location_to_brick: HashMap<Location, usize> = bricks
-            .iter()
-            .enumerate()
-            .flat_map(|(i, brick)| brick.locations().map(move |location| (location, i)))
-            .collect();
+        let location_to_brick = index(&bricks);
 
         Self {
             bricks,
@@ -34,7 +113,50 @@ impl Stack {
             .map(|&i| &self.bricks[i])
     }
 
+    pub fn bricks_below(&self, i: usize) -> HashSet<usize> {
+        self.bricks[i]
+            .locations_bottom()
+            .filter_map(|location| self.location_to_brick.get(&location.below()).copied())
+            .collect()
+    }
+
     pub fn settle(&mut self) {
+        let width = self.bricks.iter().map(|brick| brick.x.end + 1).max();
+        let depth = self.bricks.iter().map(|brick| brick.y.end + 1).max();
+
+        let Some((width, depth)) = width.zip(depth) else {
+            return;
+        };
+
+        // The highest occupied z for every (x, y) column, starting just below the floor.
+        let mut heights = vec![FLOOR - 1; width * depth];
+
+        let mut order: Vec<_> = (0..self.bricks.len()).collect();
+        order.sort_by_key(|&i| self.bricks[i].z.start);
+
+        // Bricks can only rest on bricks that started out lower, so dropping them in order of
+        // their bottom z means everything underneath has already settled.
+        for i in order {
+            let brick = &mut self.bricks[i];
+
+            let top = brick
+                .iter_xy()
+                .map(|(x, y)| heights[y * width + x])
+                .max()
+                .unwrap();
+
+            brick.z -= brick.z.start - (top + 1);
+
+            for (x, y) in brick.iter_xy() {
+                heights[y * width + x] = brick.z.end;
+            }
+        }
+
+        self.location_to_brick = index(&self.bricks);
+    }
+
+    #[cfg(test)]
+    fn settle_naive(&mut self) {
         loop {
             let mut changed = false;
 
@@ -78,7 +200,7 @@ impl Stack {
         }
     }
 
-    pub fn dependencies(&self) -> (Dependencies, Dependencies) {
+    pub fn dependencies(&self) -> (Dependencies<'_>, Dependencies<'_>) {
         self.bricks.iter().fold(
             (HashMap::new(), HashMap::new()),
             |(mut supports, mut is_supported_by), brick| {
@@ -114,15 +236,115 @@ impl std::convert::TryFrom<&str> for Stack {
     type Error = anyhow::Error;
 
     fn try_from(input: &str) -> Result<Self, Self::Error> {
-        Ok(Self::new(
-            input
-                .lines()
-                .enumerate()
-                .map(|(i, line)| {
-                    line.try_into()
-                        .with_context(|| format!("brick number {}", i + 1))
-                })
-                .collect::<Result<Vec<Brick>, _>>()?,
-        ))
+        let bricks = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                line.try_into()
+                    .with_context(|| format!("brick number {}", i + 1))
+            })
+            .collect::<Result<Vec<Brick>, _>>()?;
+
+        validate(&bricks)?;
+
+        Ok(Self::new(bricks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A small xorshift generator is plenty to shuffle bricks around without any dependencies.
+    fn random(state: &mut u64) -> usize {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state as usize
+    }
+
+    fn random_snapshot(seed: u64, len: usize) -> Stack {
+        let mut state = seed;
+        let mut occupied = HashSet::new();
+        let mut bricks = Vec::new();
+
+        while bricks.len() < len {
+            let start = Location::new(
+                random(&mut state) % 5,
+                random(&mut state) % 5,
+                FLOOR + random(&mut state) % 40,
+            );
+
+            let extent = random(&mut state) % 4;
+            let end = match random(&mut state) % 3 {
+                0 => Location::new((start.x + extent).min(4), start.y, start.z),
+                1 => Location::new(start.x, (start.y + extent).min(4), start.z),
+                _ => Location::new(start.x, start.y, start.z + extent),
+            };
+
+            let brick = Brick::new(start, end);
+
+            if brick
+                .locations()
+                .all(|location| !occupied.contains(&location))
+            {
+                occupied.extend(brick.locations());
+                bricks.push(brick);
+            }
+        }
+
+        Stack::new(bricks)
+    }
+
+    #[test]
+    fn test_settle() {
+        for seed in 1..=50 {
+            let snapshot = random_snapshot(seed, 60);
+
+            let mut expected = snapshot.clone();
+            expected.settle_naive();
+
+            let mut actual = snapshot.clone();
+            actual.settle();
+
+            assert_eq!(actual, expected, "seed {}", seed);
+            assert_eq!(
+                actual.dependencies(),
+                expected.dependencies(),
+                "seed {}",
+                seed
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        fn error(input: &str) -> Option<SnapshotError> {
+            Stack::try_from(input)
+                .err()
+                .and_then(|error| error.downcast().ok())
+        }
+
+        assert_eq!(
+            error("0,0,1~1,1,1"),
+            Some(SnapshotError::Diagonal { brick: 0 })
+        );
+
+        assert_eq!(
+            error("0,0,1~0,0,2\n1,0,0~1,2,0"),
+            Some(SnapshotError::BelowFloor { brick: 1, z: 0 })
+        );
+
+        assert_eq!(
+            error("0,0,1~2,0,1\n1,0,2~1,0,1"),
+            Some(SnapshotError::Overlap {
+                first: 0,
+                second: 1,
+                location: Location::new(1, 0, 1)
+            })
+        );
+
+        // Reversed endpoints are fine, since they describe the same brick.
+        assert!(Stack::try_from("2,0,1~0,0,1\n0,0,3~0,0,2").is_ok());
     }
 }
@@ -0,0 +1,131 @@
+use std::cmp::Reverse;
+
+use super::Stack;
+
+const GROUND: usize = 0;
+
+// Bricks are numbered from one in the graph, leaving zero for the ground that holds everything up.
+fn node(brick: usize) -> usize {
+    brick + 1
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupportGraph {
+    // The immediate dominator of every node, i.e. the closest brick (or the ground) that every
+    // chain of support for the node must pass through.
+    dominators: Vec<usize>,
+    // The ancestors of every node in the dominator tree at each power of two, used to find common
+    // dominators without walking the whole tree.
+    ancestors: Vec<Vec<usize>>,
+    depths: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl SupportGraph {
+    fn common_dominator(&self, mut a: usize, mut b: usize) -> usize {
+        if self.depths[a] < self.depths[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        let difference = self.depths[a] - self.depths[b];
+
+        for (k, ancestors) in self.ancestors.iter().enumerate() {
+            if difference & (1 << k) != 0 {
+                a = ancestors[a];
+            }
+        }
+
+        if a == b {
+            return a;
+        }
+
+        for ancestors in self.ancestors.iter().rev() {
+            if ancestors[a] != ancestors[b] {
+                a = ancestors[a];
+                b = ancestors[b];
+            }
+        }
+
+        self.ancestors[0][a]
+    }
+
+    pub fn len(&self) -> usize {
+        self.dominators.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn dominator(&self, brick: usize) -> Option<usize> {
+        match self.dominators[node(brick)] {
+            GROUND => None,
+            dominator => Some(dominator - 1),
+        }
+    }
+
+    pub fn falls(&self, brick: usize) -> usize {
+        self.sizes[node(brick)] - 1
+    }
+
+    pub fn total_falls(&self) -> usize {
+        (0..self.len()).map(|brick| self.falls(brick)).sum()
+    }
+
+    pub fn single_points_of_failure(&self, limit: usize) -> Vec<(usize, usize)> {
+        let mut bricks: Vec<_> = (0..self.len())
+            .map(|brick| (brick, self.falls(brick)))
+            .filter(|&(_, falls)| falls > 0)
+            .collect();
+
+        bricks.sort_by_key(|&(brick, falls)| (Reverse(falls), brick));
+        bricks.truncate(limit);
+
+        bricks
+    }
+}
+
+impl From<&Stack> for SupportGraph {
+    fn from(stack: &Stack) -> Self {
+        let len = stack.bricks.len() + 1;
+        let levels = (usize::BITS - len.leading_zeros()) as usize;
+
+        let mut graph = Self {
+            dominators: vec![GROUND; len],
+            ancestors: vec![vec![GROUND; len]; levels],
+            depths: vec![0; len],
+            sizes: vec![1; len],
+        };
+
+        // Support only ever comes from below, so going from the bottom up guarantees that every
+        // supporting brick already has its place in the dominator tree.
+        let mut order: Vec<_> = (0..stack.bricks.len()).collect();
+        order.sort_by_key(|&i| stack.bricks[i].z.start);
+
+        for &i in order.iter() {
+            let dominator = stack
+                .bricks_below(i)
+                .into_iter()
+                .map(node)
+                .reduce(|a, b| graph.common_dominator(a, b))
+                .unwrap_or(GROUND);
+
+            let v = node(i);
+
+            graph.dominators[v] = dominator;
+            graph.depths[v] = graph.depths[dominator] + 1;
+            graph.ancestors[0][v] = dominator;
+
+            for k in 1..levels {
+                graph.ancestors[k][v] = graph.ancestors[k - 1][graph.ancestors[k - 1][v]];
+            }
+        }
+
+        for &i in order.iter().rev() {
+            let v = node(i);
+            graph.sizes[graph.dominators[v]] += graph.sizes[v];
+        }
+
+        graph
+    }
+}